@@ -13,8 +13,11 @@ extern crate std;
 mod test;
 pub mod testutils;
 
-use soroban_auth::{Identifier, Signature};
-use soroban_sdk::{contractimpl, contracttype, BigInt, BytesN, Env};
+use soroban_auth::{verify, Identifier, Signature};
+use soroban_sdk::{
+    contracterror, contractimpl, contracttype, symbol, vec, BigInt, Bytes, BytesN, Env, IntoVal,
+    RawVal, Symbol, Vec,
+};
 
 mod token {
     soroban_sdk::contractimport!(file = "./soroban_token_spec.wasm");
@@ -25,6 +28,12 @@ mod auction {
     soroban_sdk::contractimport!(file = "./soroban_dutch_auction_contract.wasm");
 }
 
+/// Version of the storage layout written by this code
+const VERSION: u32 = 1;
+
+/// How many sealed bids an auction takes, so settling it stays within budget
+const MAX_COMMITMENTS: u32 = 32;
+
 // Perform arithmetic ops on custom types
 trait Arithmetic<Rhs = Self> {
     type Output;
@@ -32,22 +41,187 @@ trait Arithmetic<Rhs = Self> {
     fn add(self, rhs: Rhs) -> Self::Output;
 }
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Errors returned by the contract's entry points, codes are stable
+pub enum PauletteError {
+    /// The admin has already been set
+    AlreadyInitialized = 1,
+    /// The contract hasn't been initialized yet
+    NotInitialized = 2,
+    /// The bid on the office's auction failed
+    BiddingFailed = 3,
+    /// An office with the given id already exists
+    IdAlreadyExists = 4,
+    /// The office hasn't expired yet
+    NotExpired = 5,
+    /// The signature doesn't come from the admin
+    NotAuthorized = 6,
+    /// The provided nonce doesn't match the stored one
+    IncorrectNonce = 7,
+    /// Invoker signatures must be paired with a zero nonce
+    InvalidInvokerNonce = 8,
+    /// No office with the given id is for sale
+    NotForSale = 9,
+    /// No office with the given id has been bought
+    NotBought = 10,
+    /// The signature doesn't come from the office's holder
+    NotHolder = 11,
+    /// The signature doesn't come from the office's designated heir
+    NotHeir = 12,
+    /// The heir's claim window is still open, the office can't be revoked yet
+    ClaimWindowOpen = 13,
+    /// The heir's claim window has closed
+    ClaimWindowClosed = 14,
+    /// No office with the given id exists
+    OfficeNotFound = 15,
+    /// At least one period has to be paid
+    NoPeriods = 16,
+    /// The payment would extend the office past the maximum prepay horizon
+    PrepayTooLong = 17,
+    /// The grace window has closed, the tax can't be paid anymore
+    GracePeriodOver = 18,
+    /// The office is still in its grace window
+    InGracePeriod = 19,
+    /// The office's tax isn't self-assessed
+    NotSelfAssessed = 20,
+    /// The signature doesn't come from the proposed admin
+    NotPendingAdmin = 21,
+    /// The same admin signed more than once
+    DuplicateSigner = 22,
+    /// Not enough admins signed
+    ThresholdNotMet = 23,
+    /// The threshold has to be between one and the number of admins
+    InvalidThreshold = 24,
+    /// The contract is paused
+    Paused = 25,
+    /// The contract isn't paused
+    NotPaused = 26,
+    /// The storage is already at the current version
+    AlreadyMigrated = 27,
+    /// The office isn't sold through this mechanism
+    WrongMechanism = 28,
+    /// The auction's deadline has passed
+    AuctionClosed = 29,
+    /// The auction's deadline hasn't passed yet
+    AuctionOpen = 30,
+    /// The bid is below the reserve or doesn't beat the highest bid by the minimum increment
+    BidTooLow = 31,
+    /// The bidder already committed to a sealed bid
+    AlreadyCommitted = 32,
+    /// Sealed bids can't be revealed right now
+    NotRevealPhase = 33,
+    /// The bidder didn't commit to a sealed bid
+    NoCommitment = 34,
+    /// The revealed bid and salt don't match the commitment
+    HashMismatch = 35,
+    /// The revealed bid is higher than the deposit backing it
+    DepositTooLow = 36,
+    /// The office costs more than the buyer's limit
+    PriceAboveLimit = 37,
+    /// Admin sets with more than one member need a treasury to be set first
+    TreasuryNotSet = 38,
+    /// The auction already took as many sealed bids as it can settle
+    TooManyBids = 39,
+    /// English auctions need a positive minimum increment
+    InvalidIncrement = 40,
+    /// Valuations can't be negative
+    InvalidValuation = 41,
+    /// A time computation doesn't fit in a timestamp
+    Overflow = 42,
+    /// The storage of the first release is still being migrated
+    Migrating = 43,
+    /// The office is still stored as the first release stored it
+    NotMigrated = 44,
+}
+
 #[derive(Clone)]
 #[contracttype]
 /// Keys for the contract data
 pub enum DataKey {
     /// What standard token to use in the contract
     TokenId,
-    /// Contract admin
+    /// Contract admins and how many of them have to sign
     Admin,
-    /// Tax to pay to keep the office after a week
+    /// Default tax to pay to keep an office after each period
     Tax,
+    /// Default period after which an office expires
+    Period,
     /// Key for offices that are for sale
     ForSale(BytesN<16>),
     /// Key for offices that have been bought
     Bought(BytesN<16>),
     /// Admin nonce
     Nonce(Identifier),
+    /// Heir designated by the holder of a bought office
+    Heir(BytesN<16>),
+    /// How long the heir can claim an expired office before it can be revoked
+    ClaimWindow,
+    /// How far in the future holders can prepay their offices
+    MaxPrepay,
+    /// How long after expiring an office's tax can still be paid
+    Grace,
+    /// Surcharge in basis points of the tax for payments made during the grace window
+    Penalty,
+    /// How many ids a registry holds
+    RegistryLen(Registry),
+    /// Id stored at a position of a registry
+    RegistryAt(RegistrySlot),
+    /// Position of an id in a registry
+    RegistryIndex(RegistryEntry),
+    /// Admin proposed by the current one, until they accept
+    PendingAdmin,
+    /// Recipient of taxes and auction proceeds, defaults to the admin while it's a single identifier
+    Treasury,
+    /// Roles granted to an identifier
+    Roles(Identifier),
+    /// Set while the contract is paused
+    Paused,
+    /// How long taxes were blocked by past pauses, held offices expire that much later
+    PausedFor,
+    /// Version of the storage layout, missing for the first release
+    Version,
+    /// Set while the storage of the first release is being migrated in batches
+    Migrating,
+    /// Hash of the installed dutch auction wasm offices are sold with
+    AuctionWasm,
+    /// How many auctions have been deployed for an office
+    Round(BytesN<16>),
+    /// Highest bid, held in escrow, in the english auction of an office
+    Bid(BytesN<16>),
+    /// Sealed bids committed to in the auction of an office
+    Commits(BytesN<16>),
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// Lists of office ids, each entry stored under its own key
+pub enum Registry {
+    /// All the offices ever created
+    Offices,
+    /// Offices that are for sale
+    ForSale,
+    /// Offices that have been bought
+    Held,
+    /// Offices held by an identifier
+    Holdings(Identifier),
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// Position in a registry
+pub struct RegistrySlot {
+    pub registry: Registry,
+    pub index: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// Id in a registry
+pub struct RegistryEntry {
+    pub registry: Registry,
+    pub id: BytesN<16>,
 }
 
 #[derive(Clone)]
@@ -58,6 +232,34 @@ pub struct Auth {
     pub nonce: BigInt,
 }
 
+#[derive(Clone)]
+#[contracttype]
+/// Admin set, `threshold` of the `members` have to sign admin calls
+pub struct AdminSet {
+    pub members: Vec<Identifier>,
+    pub threshold: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// Pause started at `since`, taxes can't be paid if `block_tax` is set
+pub struct Pause {
+    pub since: TimeStamp,
+    pub block_tax: bool,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// Roles the admin can delegate admin calls to
+pub enum Role {
+    /// Can create new offices
+    Registrar,
+    /// Can revoke expired offices
+    Collector,
+    /// Can change the tax parameters
+    Treasurer,
+}
+
 #[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug)]
 #[contracttype]
 /// Timestamp type to enforce explicitness
@@ -67,33 +269,236 @@ impl TimeStamp {
     fn current(e: &Env) -> Self {
         Self(e.ledger().timestamp())
     }
+
+    fn mul(self, times: u32) -> Result<Self, PauletteError> {
+        self.0
+            .checked_mul(times as u64)
+            .map(Self)
+            .ok_or(PauletteError::Overflow)
+    }
 }
 
 impl Arithmetic<TimeStamp> for TimeStamp {
-    type Output = TimeStamp;
+    type Output = Result<TimeStamp, PauletteError>;
 
-    fn add(self, other: Self) -> Self {
-        Self(self.0 + other.0)
+    fn add(self, other: Self) -> Self::Output {
+        self.0
+            .checked_add(other.0)
+            .map(Self)
+            .ok_or(PauletteError::Overflow)
     }
 }
 
+#[derive(Clone)]
+#[contracttype]
+/// How the tax to pay each period for an office is computed
+pub enum Tax {
+    /// Fixed amount
+    Flat(BigInt),
+    /// Basis points of the valuation declared by the holder, anyone can force-buy the office at that valuation
+    SelfAssessed(u32),
+    /// Basis points of the price the office was bought for
+    Proportional(u32),
+}
+
 #[derive(Clone)]
 #[contracttype]
 /// Office struct, stored with key DataKey::Bought(id)
 pub struct Office {
     pub user: Identifier,
     pub expires: TimeStamp,
+    pub tax: Tax,
+    pub period: TimeStamp,
+    pub price: BigInt,
+    pub valuation: BigInt,
 }
 
-fn new_auction(e: &Env, id: BytesN<32>, price: BigInt, min_price: BigInt, slope: BigInt) {
-    let client = auction::Client::new(e, id);
+#[derive(Clone)]
+#[contracttype]
+/// Office as stored by the first release, before taxes and periods were kept per office
+pub struct OfficeV0 {
+    pub user: Identifier,
+    pub expires: TimeStamp,
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// Parameters of the dutch auction an office is sold with
+pub struct DutchAuction {
+    pub price: BigInt,
+    pub min_price: BigInt,
+    pub slope: BigInt,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// Parameters of the english auction an office is sold with, bids are open for `duration`
+pub struct EnglishAuction {
+    pub reserve: BigInt,
+    pub increment: BigInt,
+    pub duration: TimeStamp,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// Parameters of the sealed-bid auction an office is sold with, bids are committed for `commit` and then revealed for `reveal`
+pub struct SealedAuction {
+    pub reserve: BigInt,
+    pub commit: TimeStamp,
+    pub reveal: TimeStamp,
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// How an office is put up for sale
+pub enum SaleParams {
+    /// Through a dutch auction deployed for the office
+    Dutch(DutchAuction),
+    /// At a fixed price
+    FixedPrice(BigInt),
+    /// Through an english auction run by this contract
+    English(EnglishAuction),
+    /// Through a sealed-bid second-price auction run by this contract
+    Sealed(SealedAuction),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// How an office is being sold
+pub enum SaleMechanism {
+    /// Through the dutch auction contract with the given id
+    Dutch(BytesN<32>),
+    /// At a fixed price
+    FixedPrice(BigInt),
+    /// Through an english auction run by this contract
+    English(EnglishSale),
+    /// Through a sealed-bid second-price auction run by this contract
+    Sealed(SealedSale),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// Sealed-bid auction an office is being sold through and when its phases end
+pub struct SealedSale {
+    pub params: SealedAuction,
+    pub commit_end: TimeStamp,
+    pub reveal_end: TimeStamp,
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// Sealed bid and the deposit backing it, `amount` is only known once revealed
+pub struct Commitment {
+    pub bidder: Identifier,
+    pub hash: BytesN<32>,
+    pub deposit: BigInt,
+    pub revealed: bool,
+    pub amount: BigInt,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// English auction an office is being sold through and when it closes
+pub struct EnglishSale {
+    pub params: EnglishAuction,
+    pub deadline: TimeStamp,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// Bid in an english auction, stored with key DataKey::Bid(id)
+pub struct Bid {
+    pub bidder: Identifier,
+    pub amount: BigInt,
+}
+
+#[derive(Clone)]
+#[contracttype]
+/// Sale struct, stored with key DataKey::ForSale(id)
+pub struct Sale {
+    pub mechanism: SaleMechanism,
+    pub tax: Tax,
+    pub period: TimeStamp,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// How an office is sold and its current price
+pub struct Listing {
+    pub mechanism: SaleMechanism,
+    pub price: BigInt,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// Holder of an office and when it expires (or expired)
+pub struct Tenure {
+    pub user: Identifier,
+    pub expires: TimeStamp,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+/// Status of an office, returned by get_office
+pub enum OfficeStatus {
+    /// The office is being auctioned
+    ForSale(Listing),
+    /// The office is held and its tax is paid
+    Held(Tenure),
+    /// The office is held but its tax is overdue
+    Expired(Tenure),
+    /// No office with the given id exists
+    Unknown,
+}
+
+fn put_auction_wasm(e: &Env, wasm_hash: BytesN<32>) {
+    let key = DataKey::AuctionWasm;
+    e.data().set(key, wasm_hash);
+}
+
+fn get_auction_wasm(e: &Env) -> Result<BytesN<32>, PauletteError> {
+    let key = DataKey::AuctionWasm;
+    e.data()
+        .get(key)
+        .ok_or(PauletteError::NotInitialized)
+        .map(|wasm_hash| wasm_hash.unwrap())
+}
+
+fn next_round(e: &Env, id: &BytesN<16>) -> u32 {
+    let key = DataKey::Round(id.clone());
+    let round = e.data().get(key.clone()).unwrap_or(Ok(0)).unwrap();
+    e.data().set(key, round + 1);
+    round
+}
+
+// the office id followed by the round, so every auction of every office gets its own address
+fn auction_salt(e: &Env, id: &BytesN<16>, round: u32) -> BytesN<32> {
+    let mut salt = [0; 32];
+    salt[..16].copy_from_slice(&id.to_array());
+    salt[16..20].copy_from_slice(&round.to_be_bytes());
+    BytesN::from_array(e, &salt)
+}
+
+fn new_auction(
+    e: &Env,
+    id: &BytesN<16>,
+    params: DutchAuction,
+) -> Result<BytesN<32>, PauletteError> {
+    let salt = auction_salt(e, id, next_round(e, id));
+    let auction_id = e
+        .deployer()
+        .with_current_contract(&salt)
+        .deploy(&get_auction_wasm(e)?);
+
+    let client = auction::Client::new(e, auction_id.clone());
     client.initialize(
-        &read_administrator(e),
+        &read_treasury(e)?,
         &get_token_id(e),
-        &price,
-        &min_price,
-        &slope,
+        &params.price,
+        &params.min_price,
+        &params.slope,
     );
+    Ok(auction_id)
 }
 
 fn bid_auction(e: &Env, id: BytesN<32>, buyer: Identifier) -> bool {
@@ -101,14 +506,28 @@ fn bid_auction(e: &Env, id: BytesN<32>, buyer: Identifier) -> bool {
     client.buy(&buyer)
 }
 
-fn put_bought(e: &Env, id: BytesN<16>, bought: Office) {
+fn auction_price(e: &Env, id: BytesN<32>) -> BigInt {
+    let client = auction::Client::new(e, id);
+    client.get_price()
+}
+
+// expiries are stored as if the time taxes were blocked never passed
+fn put_bought(e: &Env, id: BytesN<16>, mut bought: Office) -> Result<(), PauletteError> {
     let key = DataKey::Bought(id);
+    bought.expires = TimeStamp(bought.expires.0.saturating_sub(pause_offset(e)?.0));
     e.data().set(key, bought);
+    Ok(())
 }
 
-fn get_bought(e: &Env, id: BytesN<16>) -> Office {
+fn get_bought(e: &Env, id: BytesN<16>) -> Result<Office, PauletteError> {
     let key = DataKey::Bought(id);
-    e.data().get(key).unwrap().unwrap()
+    let mut office: Office = e
+        .data()
+        .get(key)
+        .ok_or(PauletteError::NotBought)?
+        .map_err(|_| PauletteError::NotMigrated)?;
+    office.expires = office.expires.add(pause_offset(e)?)?;
+    Ok(office)
 }
 
 fn remove_bought(e: &Env, id: BytesN<16>) {
@@ -121,14 +540,135 @@ fn remove_for_sale(e: &Env, id: BytesN<16>) {
     e.data().remove(key);
 }
 
-fn put_for_sale(e: &Env, id: BytesN<16>, auction: BytesN<32>) {
+fn put_for_sale(e: &Env, id: BytesN<16>, sale: Sale) {
     let key = DataKey::ForSale(id);
-    e.data().set(key, auction)
+    e.data().set(key, sale)
 }
 
-fn get_for_sale(e: &Env, id: BytesN<16>) -> BytesN<32> {
+fn get_for_sale(e: &Env, id: BytesN<16>) -> Result<Sale, PauletteError> {
     let key = DataKey::ForSale(id);
-    e.data().get(key).unwrap().unwrap()
+    e.data()
+        .get(key)
+        .ok_or(PauletteError::NotForSale)?
+        .map_err(|_| PauletteError::NotMigrated)
+}
+
+fn registry_len(e: &Env, registry: Registry) -> u32 {
+    e.data()
+        .get(DataKey::RegistryLen(registry))
+        .unwrap_or(Ok(0))
+        .unwrap()
+}
+
+fn registry_at(e: &Env, registry: Registry, index: u32) -> BytesN<16> {
+    e.data()
+        .get(DataKey::RegistryAt(RegistrySlot { registry, index }))
+        .unwrap()
+        .unwrap()
+}
+
+fn has_id(e: &Env, registry: Registry, id: BytesN<16>) -> bool {
+    e.data()
+        .has(DataKey::RegistryIndex(RegistryEntry { registry, id }))
+}
+
+fn add_id(e: &Env, registry: Registry, id: BytesN<16>) {
+    let index = registry_len(e, registry.clone());
+    e.data().set(
+        DataKey::RegistryAt(RegistrySlot {
+            registry: registry.clone(),
+            index,
+        }),
+        id.clone(),
+    );
+    e.data().set(
+        DataKey::RegistryIndex(RegistryEntry {
+            registry: registry.clone(),
+            id,
+        }),
+        index,
+    );
+    e.data().set(DataKey::RegistryLen(registry), index + 1);
+}
+
+// moves the last id into the freed slot, so removing never touches more than a few keys
+fn remove_id(e: &Env, registry: Registry, id: BytesN<16>) {
+    let entry = DataKey::RegistryIndex(RegistryEntry {
+        registry: registry.clone(),
+        id,
+    });
+    let index: u32 = match e.data().get(entry.clone()) {
+        Some(index) => index.unwrap(),
+        None => return,
+    };
+    let last = registry_len(e, registry.clone()) - 1;
+    if index != last {
+        let moved = registry_at(e, registry.clone(), last);
+        e.data().set(
+            DataKey::RegistryAt(RegistrySlot {
+                registry: registry.clone(),
+                index,
+            }),
+            moved.clone(),
+        );
+        e.data().set(
+            DataKey::RegistryIndex(RegistryEntry {
+                registry: registry.clone(),
+                id: moved,
+            }),
+            index,
+        );
+    }
+
+    e.data().remove(DataKey::RegistryAt(RegistrySlot {
+        registry: registry.clone(),
+        index: last,
+    }));
+    e.data().remove(entry);
+    e.data().set(DataKey::RegistryLen(registry), last);
+}
+
+fn move_holding(e: &Env, id: BytesN<16>, from: Identifier, to: Identifier) {
+    remove_id(e, Registry::Holdings(from), id.clone());
+    add_id(e, Registry::Holdings(to), id);
+}
+
+fn page_ids(e: &Env, registry: Registry, start: u32, limit: u32) -> Vec<BytesN<16>> {
+    let end = registry_len(e, registry.clone()).min(start.saturating_add(limit));
+    let mut page = Vec::new(e);
+    for index in start..end {
+        page.push_back(registry_at(e, registry.clone(), index));
+    }
+    page
+}
+
+fn put_heir(e: &Env, id: BytesN<16>, heir: Identifier) {
+    let key = DataKey::Heir(id);
+    e.data().set(key, heir);
+}
+
+fn get_heir(e: &Env, id: BytesN<16>) -> Option<Identifier> {
+    let key = DataKey::Heir(id);
+    e.data().get(key).map(|heir| heir.unwrap())
+}
+
+fn remove_heir(e: &Env, id: BytesN<16>) {
+    let key = DataKey::Heir(id);
+    e.data().remove(key);
+}
+
+fn put_claim_window(e: &Env, window: TimeStamp) {
+    let key = DataKey::ClaimWindow;
+    e.data().set(key, window);
+}
+
+// defaults to the contract's period when the admin hasn't set it
+fn get_claim_window(e: &Env) -> TimeStamp {
+    let key = DataKey::ClaimWindow;
+    e.data()
+        .get(key)
+        .unwrap_or_else(|| Ok(get_default_period(e)))
+        .unwrap()
 }
 
 fn put_token_id(e: &Env, token_id: BytesN<32>) {
@@ -136,237 +676,1894 @@ fn put_token_id(e: &Env, token_id: BytesN<32>) {
     e.data().set(key, token_id);
 }
 
-fn put_tax(e: &Env, amount: BigInt) {
+fn put_default_tax(e: &Env, amount: BigInt) {
     let key = DataKey::Tax;
     e.data().set(key, amount);
 }
 
-fn get_tax(e: &Env) -> BigInt {
+fn get_default_tax(e: &Env) -> BigInt {
     let key = DataKey::Tax;
     e.data().get(key).unwrap().unwrap()
 }
 
-fn get_token_id(e: &Env) -> BytesN<32> {
-    let key = DataKey::TokenId;
-    e.data().get(key).unwrap().unwrap()
+fn put_version(e: &Env, version: u32) {
+    let key = DataKey::Version;
+    e.data().set(key, version);
 }
 
-fn transfer_to_admin(e: &Env, from: Identifier, amount: BigInt) {
-    let client = token::Client::new(e, get_token_id(e));
-
-    client.xfer_from(
-        &Signature::Invoker,
-        &BigInt::zero(e),
-        &from,
-        &read_administrator(e),
-        &amount,
-    )
+fn get_version(e: &Env) -> u32 {
+    let key = DataKey::Version;
+    e.data().get(key).unwrap_or(Ok(0)).unwrap()
 }
 
-fn has_administrator(e: &Env) -> bool {
+// the first release had a single admin, stored as a plain identifier
+fn check_admin_v0(
+    e: &Env,
+    auth: &Auth,
+    function: Symbol,
+    args: Vec<RawVal>,
+) -> Result<Identifier, PauletteError> {
     let key = DataKey::Admin;
-    e.data().has(key)
+    let admin: Identifier = e
+        .data()
+        .get(key)
+        .ok_or(PauletteError::NotInitialized)?
+        .unwrap();
+    if auth.sig.identifier(e) != admin {
+        return Err(PauletteError::NotAuthorized);
+    }
+
+    verify_auth(e, auth, function, args)?;
+    Ok(admin)
 }
 
-fn read_administrator(e: &Env) -> Identifier {
-    let key = DataKey::Admin;
-    e.data().get_unchecked(key).unwrap()
+// rewrites an office stored by the first release, which only knew weekly flat taxes
+fn migrate_office_v0(e: &Env, id: BytesN<16>) -> Result<(), PauletteError> {
+    let tax = Tax::Flat(get_default_tax(e));
+    let period = get_default_period(e);
+
+    if let Some(office) = e.data().get::<_, OfficeV0>(DataKey::Bought(id.clone())) {
+        let office = office.unwrap();
+        add_id(e, Registry::Held, id.clone());
+        add_id(e, Registry::Holdings(office.user.clone()), id.clone());
+        put_bought(
+            e,
+            id.clone(),
+            Office {
+                user: office.user,
+                expires: office.expires,
+                tax,
+                period,
+                price: BigInt::zero(e),
+                valuation: BigInt::zero(e),
+            },
+        )?;
+    } else if let Some(auction) = e.data().get::<_, BytesN<32>>(DataKey::ForSale(id.clone())) {
+        add_id(e, Registry::ForSale, id.clone());
+        put_for_sale(
+            e,
+            id.clone(),
+            Sale {
+                mechanism: SaleMechanism::Dutch(auction.unwrap()),
+                tax,
+                period,
+            },
+        );
+    } else {
+        return Ok(());
+    }
+    add_id(e, Registry::Offices, id);
+    Ok(())
 }
 
-fn write_administrator(e: &Env, id: Identifier) {
-    let key = DataKey::Admin;
-    e.data().set(key, id);
+fn put_default_period(e: &Env, period: TimeStamp) {
+    let key = DataKey::Period;
+    e.data().set(key, period);
 }
 
-fn check_admin(e: &Env, auth: &Signature) {
-    let auth_id = auth.identifier(e);
-    if auth_id != read_administrator(e) {
-        panic!("not authorized by admin")
-    }
+fn get_default_period(e: &Env) -> TimeStamp {
+    let key = DataKey::Period;
+    e.data().get(key).unwrap().unwrap()
 }
 
-fn read_nonce(e: &Env, id: &Identifier) -> BigInt {
-    let key = DataKey::Nonce(id.clone());
+fn put_max_prepay(e: &Env, horizon: TimeStamp) {
+    let key = DataKey::MaxPrepay;
+    e.data().set(key, horizon);
+}
+
+fn get_max_prepay(e: &Env) -> Option<TimeStamp> {
+    let key = DataKey::MaxPrepay;
+    e.data().get(key).map(|horizon| horizon.unwrap())
+}
+
+fn put_grace(e: &Env, window: TimeStamp, penalty: u32) {
+    e.data().set(DataKey::Grace, window);
+    e.data().set(DataKey::Penalty, penalty);
+}
+
+fn get_grace(e: &Env) -> TimeStamp {
+    let key = DataKey::Grace;
     e.data()
         .get(key)
-        .unwrap_or_else(|| Ok(BigInt::zero(e)))
+        .unwrap_or_else(|| Ok(TimeStamp(0)))
         .unwrap()
 }
 
-fn verify_and_consume_nonce(e: &Env, auth: &Signature, expected_nonce: &BigInt) {
-    match auth {
-        Signature::Invoker => {
-            if BigInt::zero(&e) != expected_nonce {
-                panic!("nonce should be zero for Invoker")
-            }
-            return;
-        }
-        _ => {}
-    }
+fn get_penalty(e: &Env, tax: &BigInt) -> BigInt {
+    let key = DataKey::Penalty;
+    let penalty: u32 = e.data().get(key).unwrap_or(Ok(0)).unwrap();
+    basis_points(e, tax, penalty)
+}
 
-    let id = auth.identifier(&e);
-    let key = DataKey::Nonce(id.clone());
-    let nonce = read_nonce(e, &id);
+fn grace_end(e: &Env, office: &Office) -> Result<TimeStamp, PauletteError> {
+    office.expires.clone().add(get_grace(e))
+}
 
-    if nonce != expected_nonce {
-        panic!("incorrect nonce")
-    }
-    e.data().set(key, &nonce + 1);
+fn basis_points(e: &Env, amount: &BigInt, rate: u32) -> BigInt {
+    amount.clone() * BigInt::from_u32(e, rate) / BigInt::from_u32(e, 10000)
 }
 
-fn make_new_office(
-    e: &Env,
-    id: BytesN<16>,
-    auction: BytesN<32>,
-    price: BigInt,
-    min_price: BigInt,
-    slope: BigInt,
-) {
-    new_auction(e, auction.clone(), price, min_price, slope);
-    put_for_sale(e, id, auction);
+fn office_tax(e: &Env, office: &Office) -> BigInt {
+    match &office.tax {
+        Tax::Flat(amount) => amount.clone(),
+        Tax::SelfAssessed(rate) => basis_points(e, &office.valuation, *rate),
+        Tax::Proportional(rate) => basis_points(e, &office.price, *rate),
+    }
 }
 
-fn get_office_price(e: &Env, id: BytesN<16>) -> BigInt {
-    let auction_id = get_for_sale(e, id);
-    let client = auction::Client::new(e, auction_id);
+// raising the valuation doesn't lower the tax already paid, the time it covers shrinks instead
+fn revalue(e: &Env, office: &mut Office, valuation: BigInt) -> Result<(), PauletteError> {
+    if valuation < BigInt::zero(e) {
+        return Err(PauletteError::InvalidValuation);
+    }
 
-    client.get_price()
+    let now = TimeStamp::current(e);
+    let paid = office_tax(e, office);
+    office.valuation = valuation;
+    let tax = office_tax(e, office);
+
+    if office.expires > now && tax > paid {
+        let covered = BigInt::from_u64(e, office.expires.0 - now.0) * paid / tax;
+        office.expires = now.add(TimeStamp(covered.to_u64()))?;
+    }
+    Ok(())
 }
 
-pub trait PauletteContractTrait {
-    /// Sets the admin and the Royal vault's token id
-    fn initialize(e: Env, admin: Identifier, token_id: BytesN<32>, tax: BigInt);
+fn get_token_id(e: &Env) -> BytesN<32> {
+    let key = DataKey::TokenId;
+    e.data().get(key).unwrap().unwrap()
+}
 
-    /// Returns the nonce for the admin
-    fn nonce(e: Env) -> BigInt;
+fn transfer_from(e: &Env, from: Identifier, to: Identifier, amount: BigInt) {
+    let client = token::Client::new(e, get_token_id(e));
 
-    /// Call to buy an office
-    fn buy(e: Env, id: BytesN<16>, buyer: Identifier);
+    client.xfer_from(&Signature::Invoker, &BigInt::zero(e), &from, &to, &amount)
+}
 
-    /// Call to pay taxes for a given office
-    fn pay_tax(e: Env, id: BytesN<16>, payer: Identifier);
+// pays out of the tokens the contract holds in escrow
+fn transfer_out(e: &Env, to: Identifier, amount: BigInt) {
+    let client = token::Client::new(e, get_token_id(e));
 
-    /// Query the price of a given office
-    fn get_price(e: Env, id: BytesN<16>) -> BigInt;
+    client.xfer(&Signature::Invoker, &BigInt::zero(e), &to, &amount)
+}
 
-    /// Create a new office (requires admin auth)
-    fn new_office(
-        e: Env,
-        admin: Auth,
-        id: BytesN<16>,
-        auction: BytesN<32>,
-        price: BigInt,
-        min_price: BigInt,
-        slope: BigInt,
+fn escrow(e: &Env, from: Identifier, amount: BigInt) {
+    transfer_from(
+        e,
+        from,
+        Identifier::Contract(e.get_current_contract()),
+        amount,
     );
+}
 
-    /// remove office from Bought, add it to ForSale, create new dutch auction contract with the given ID
-    fn revoke(
-        e: Env,
-        admin: Auth,
-        id: BytesN<16>,
-        auction: BytesN<32>,
-        price: BigInt,
-        min_price: BigInt,
-        slope: BigInt,
-    );
+fn transfer_to_treasury(e: &Env, from: Identifier, amount: BigInt) -> Result<(), PauletteError> {
+    transfer_from(e, from, read_treasury(e)?, amount);
+    Ok(())
 }
 
-pub struct PauletteContract;
+fn put_pause(e: &Env, pause: Pause) {
+    let key = DataKey::Paused;
+    e.data().set(key, pause);
+}
 
-#[contractimpl]
-impl PauletteContractTrait for PauletteContract {
-    fn initialize(e: Env, admin: Identifier, token_id: BytesN<32>, tax: BigInt) {
-        if has_administrator(&e) {
-            panic!("admin is already set");
-        }
+fn get_pause(e: &Env) -> Option<Pause> {
+    let key = DataKey::Paused;
+    e.data().get(key).map(|pause| pause.unwrap())
+}
 
-        write_administrator(&e, admin);
-        put_token_id(&e, token_id);
-        put_tax(&e, tax);
+fn remove_pause(e: &Env) {
+    let key = DataKey::Paused;
+    e.data().remove(key);
+}
+
+// offices that weren't carried over yet can't be read
+fn check_not_migrating(e: &Env) -> Result<(), PauletteError> {
+    if e.data().has(DataKey::Migrating) {
+        return Err(PauletteError::Migrating);
     }
+    Ok(())
+}
 
-    fn nonce(e: Env) -> BigInt {
-        read_nonce(&e, &read_administrator(&e))
+fn check_not_paused(e: &Env) -> Result<(), PauletteError> {
+    match get_pause(e) {
+        Some(_) => Err(PauletteError::Paused),
+        None => Ok(()),
     }
+}
 
-    fn buy(e: Env, id: BytesN<16>, buyer: Identifier) {
-        let auction_id = get_for_sale(&e, id.clone());
-        let auction_result = bid_auction(&e, auction_id, buyer.clone());
+fn check_tax_not_paused(e: &Env) -> Result<(), PauletteError> {
+    match get_pause(e) {
+        Some(pause) if pause.block_tax => Err(PauletteError::Paused),
+        _ => Ok(()),
+    }
+}
 
-        // explicit handle
-        if !auction_result {
-            panic!("bidding failed")
-        }
+fn put_paused_for(e: &Env, duration: TimeStamp) {
+    let key = DataKey::PausedFor;
+    e.data().set(key, duration);
+}
 
-        remove_for_sale(&e, id.clone());
-        put_bought(
-            &e,
-            id,
-            Office {
-                user: buyer,
-                expires: TimeStamp::current(&e).add(TimeStamp(604800)),
-            },
-        )
-    }
+fn get_paused_for(e: &Env) -> TimeStamp {
+    let key = DataKey::PausedFor;
+    e.data()
+        .get(key)
+        .unwrap_or_else(|| Ok(TimeStamp(0)))
+        .unwrap()
+}
 
-    // the contract doesn't care if its the user who pays the office, just that someone is.
-    fn pay_tax(e: Env, id: BytesN<16>, payer: Identifier) {
-        transfer_to_admin(&e, payer, get_tax(&e));
-        let mut office = get_bought(&e, id.clone());
+// holders can't pay while taxes are blocked, so their offices last that much longer, the current pause included
+fn pause_offset(e: &Env) -> Result<TimeStamp, PauletteError> {
+    let paused_for = get_paused_for(e);
+    match get_pause(e) {
+        Some(pause) if pause.block_tax => {
+            paused_for.add(TimeStamp(TimeStamp::current(e).0 - pause.since.0))
+        }
+        _ => Ok(paused_for),
+    }
+}
 
-        // dilemma: allow to pay taxes even after they have expired if the admin doesn't revoke the office?
-        office.expires = office.expires.add(TimeStamp(604800));
+fn write_treasury(e: &Env, id: Identifier) {
+    let key = DataKey::Treasury;
+    e.data().set(key, id);
+}
 
-        put_bought(&e, id, office);
+fn read_treasury(e: &Env) -> Result<Identifier, PauletteError> {
+    let key = DataKey::Treasury;
+    match e.data().get(key) {
+        Some(treasury) => Ok(treasury.unwrap()),
+        None => read_first_administrator(e),
     }
+}
 
+fn has_administrator(e: &Env) -> bool {
+    let key = DataKey::Admin;
+    e.data().has(key)
+}
+
+fn read_administrator(e: &Env) -> Result<AdminSet, PauletteError> {
+    let key = DataKey::Admin;
+    e.data()
+        .get(key)
+        .ok_or(PauletteError::NotInitialized)
+        .map(|admin| admin.unwrap())
+}
+
+fn read_first_administrator(e: &Env) -> Result<Identifier, PauletteError> {
+    read_administrator(e)?
+        .members
+        .iter_unchecked()
+        .next()
+        .ok_or(PauletteError::NotInitialized)
+}
+
+fn write_administrator(e: &Env, admin: AdminSet) -> Result<(), PauletteError> {
+    if admin.threshold == 0 || admin.threshold > admin.members.len() {
+        return Err(PauletteError::InvalidThreshold);
+    }
+
+    // the treasury can't fall back to any single member of a multisig
+    if admin.members.len() > 1 && !e.data().has(DataKey::Treasury) {
+        return Err(PauletteError::TreasuryNotSet);
+    }
+
+    let key = DataKey::Admin;
+    e.data().set(key, admin);
+    Ok(())
+}
+
+fn put_pending_admin(e: &Env, admin: AdminSet) {
+    let key = DataKey::PendingAdmin;
+    e.data().set(key, admin);
+}
+
+fn get_pending_admin(e: &Env) -> Result<AdminSet, PauletteError> {
+    let key = DataKey::PendingAdmin;
+    e.data()
+        .get(key)
+        .ok_or(PauletteError::NotPendingAdmin)
+        .map(|admin| admin.unwrap())
+}
+
+fn remove_pending_admin(e: &Env) {
+    let key = DataKey::PendingAdmin;
+    e.data().remove(key);
+}
+
+fn contains(ids: &Vec<Identifier>, id: &Identifier) -> bool {
+    ids.iter_unchecked().any(|other| &other == id)
+}
+
+// every auth has to be signed by a distinct member of the set, and at least `threshold` of them must have signed
+fn check_signers(
+    e: &Env,
+    admin: &AdminSet,
+    auths: &Vec<Auth>,
+    not_member: PauletteError,
+    function: Symbol,
+    args: Vec<RawVal>,
+) -> Result<(), PauletteError> {
+    let mut signers = Vec::new(e);
+    for auth in auths.iter_unchecked() {
+        let auth_id = auth.sig.identifier(e);
+        if !contains(&admin.members, &auth_id) {
+            return Err(not_member);
+        }
+
+        if contains(&signers, &auth_id) {
+            return Err(PauletteError::DuplicateSigner);
+        }
+
+        verify_auth(e, &auth, function, args.clone())?;
+        signers.push_back(auth_id);
+    }
+
+    if signers.len() < admin.threshold {
+        return Err(PauletteError::ThresholdNotMet);
+    }
+    Ok(())
+}
+
+fn check_admin(
+    e: &Env,
+    auths: &Vec<Auth>,
+    function: Symbol,
+    args: Vec<RawVal>,
+) -> Result<(), PauletteError> {
+    check_signers(
+        e,
+        &read_administrator(e)?,
+        auths,
+        PauletteError::NotAuthorized,
+        function,
+        args,
+    )
+}
+
+fn read_roles(e: &Env, id: &Identifier) -> Vec<Role> {
+    let key = DataKey::Roles(id.clone());
+    e.data()
+        .get(key)
+        .unwrap_or_else(|| Ok(Vec::new(e)))
+        .unwrap()
+}
+
+fn write_roles(e: &Env, id: Identifier, roles: Vec<Role>) {
+    let key = DataKey::Roles(id);
+    if roles.is_empty() {
+        e.data().remove(key);
+    } else {
+        e.data().set(key, roles);
+    }
+}
+
+fn holds_role(e: &Env, id: &Identifier, role: Role) -> bool {
+    read_roles(e, id)
+        .iter_unchecked()
+        .any(|other| other == role)
+}
+
+// a single signer holding the role is enough, otherwise the call needs the admins
+fn check_role(
+    e: &Env,
+    role: Role,
+    auths: &Vec<Auth>,
+    function: Symbol,
+    args: Vec<RawVal>,
+) -> Result<(), PauletteError> {
+    if auths.len() == 1 {
+        let auth = auths.iter_unchecked().next().unwrap();
+        if holds_role(e, &auth.sig.identifier(e), role) {
+            return verify_auth(e, &auth, function, args);
+        }
+    }
+    check_admin(e, auths, function, args)
+}
+
+fn check_heir(e: &Env, auth: &Signature, id: BytesN<16>) -> Result<Identifier, PauletteError> {
+    let auth_id = auth.identifier(e);
+    match get_heir(e, id) {
+        Some(heir) if heir == auth_id => Ok(heir),
+        _ => Err(PauletteError::NotHeir),
+    }
+}
+
+fn check_holder(e: &Env, auth: &Signature, office: &Office) -> Result<(), PauletteError> {
+    let auth_id = auth.identifier(e);
+    if auth_id != office.user {
+        return Err(PauletteError::NotHolder);
+    }
+    Ok(())
+}
+
+fn read_nonce(e: &Env, id: &Identifier) -> BigInt {
+    let key = DataKey::Nonce(id.clone());
+    e.data()
+        .get(key)
+        .unwrap_or_else(|| Ok(BigInt::zero(e)))
+        .unwrap()
+}
+
+fn verify_and_consume_nonce(
+    e: &Env,
+    auth: &Signature,
+    expected_nonce: &BigInt,
+) -> Result<(), PauletteError> {
+    match auth {
+        Signature::Invoker => {
+            if BigInt::zero(&e) != expected_nonce {
+                return Err(PauletteError::InvalidInvokerNonce);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let id = auth.identifier(&e);
+    let key = DataKey::Nonce(id.clone());
+    let nonce = read_nonce(e, &id);
+
+    if nonce != expected_nonce {
+        return Err(PauletteError::IncorrectNonce);
+    }
+    e.data().set(key, &nonce + 1);
+    Ok(())
+}
+
+// the signature has to cover the function, its arguments and the nonce, which is then consumed
+fn verify_auth(
+    e: &Env,
+    auth: &Auth,
+    function: Symbol,
+    args: Vec<RawVal>,
+) -> Result<(), PauletteError> {
+    verify_and_consume_nonce(e, &auth.sig, &auth.nonce)?;
+
+    let mut args = args;
+    args.push_back(auth.nonce.clone().into_val(e));
+    verify(e, &auth.sig, function, args);
+    Ok(())
+}
+
+fn make_new_office(
+    e: &Env,
+    id: BytesN<16>,
+    params: SaleParams,
+    tax: Option<Tax>,
+    period: Option<TimeStamp>,
+) -> Result<SaleMechanism, PauletteError> {
+    let mechanism = match params {
+        SaleParams::Dutch(params) => SaleMechanism::Dutch(new_auction(e, &id, params)?),
+        SaleParams::FixedPrice(price) => SaleMechanism::FixedPrice(price),
+        SaleParams::English(params) => {
+            // otherwise an equal bid could take the lead
+            if params.increment <= BigInt::zero(e) {
+                return Err(PauletteError::InvalidIncrement);
+            }
+            let deadline = TimeStamp::current(e).add(params.duration.clone())?;
+            SaleMechanism::English(EnglishSale { params, deadline })
+        }
+        SaleParams::Sealed(params) => SaleMechanism::Sealed(sealed_sale(e, params)?),
+    };
+
+    add_id(e, Registry::ForSale, id.clone());
+    put_for_sale(
+        e,
+        id,
+        Sale {
+            mechanism: mechanism.clone(),
+            tax: tax.unwrap_or_else(|| Tax::Flat(get_default_tax(e))),
+            period: period.unwrap_or_else(|| get_default_period(e)),
+        },
+    );
+    Ok(mechanism)
+}
+
+fn put_bid(e: &Env, id: BytesN<16>, bid: Bid) {
+    let key = DataKey::Bid(id);
+    e.data().set(key, bid);
+}
+
+fn get_bid(e: &Env, id: BytesN<16>) -> Option<Bid> {
+    let key = DataKey::Bid(id);
+    e.data().get(key).map(|bid| bid.unwrap())
+}
+
+fn remove_bid(e: &Env, id: BytesN<16>) {
+    let key = DataKey::Bid(id);
+    e.data().remove(key);
+}
+
+fn get_commitments(e: &Env, id: BytesN<16>) -> Vec<Commitment> {
+    let key = DataKey::Commits(id);
+    e.data()
+        .get(key)
+        .unwrap_or_else(|| Ok(Vec::new(e)))
+        .unwrap()
+}
+
+fn put_commitments(e: &Env, id: BytesN<16>, commitments: Vec<Commitment>) {
+    let key = DataKey::Commits(id);
+    e.data().set(key, commitments);
+}
+
+fn remove_commitments(e: &Env, id: BytesN<16>) {
+    let key = DataKey::Commits(id);
+    e.data().remove(key);
+}
+
+/// Hash a sealed bid commits to: the sha256 of the big-endian amount followed by the salt
+pub fn bid_hash(e: &Env, amount: &BigInt, salt: &BytesN<32>) -> BytesN<32> {
+    let mut data = amount.to_bytes_be();
+    data.append(&Bytes::from(salt.clone()));
+    e.compute_hash_sha256(&data)
+}
+
+fn sealed_sale(e: &Env, params: SealedAuction) -> Result<SealedSale, PauletteError> {
+    let commit_end = TimeStamp::current(e).add(params.commit.clone())?;
+    let reveal_end = commit_end.clone().add(params.reveal.clone())?;
+    Ok(SealedSale {
+        params,
+        commit_end,
+        reveal_end,
+    })
+}
+
+// english auctions are priced at their highest bid, or their reserve until someone bids, sealed ones at their reserve
+fn sale_price(e: &Env, id: &BytesN<16>, mechanism: &SaleMechanism) -> BigInt {
+    match mechanism {
+        SaleMechanism::Dutch(auction) => auction_price(e, auction.clone()),
+        SaleMechanism::FixedPrice(price) => price.clone(),
+        SaleMechanism::English(sale) => match get_bid(e, id.clone()) {
+            Some(bid) => bid.amount,
+            None => sale.params.reserve.clone(),
+        },
+        SaleMechanism::Sealed(sale) => sale.params.reserve.clone(),
+    }
+}
+
+// collects the payment for the office from the buyer, returning the price paid
+fn pay_sale(
+    e: &Env,
+    mechanism: &SaleMechanism,
+    buyer: &Identifier,
+) -> Result<BigInt, PauletteError> {
+    match mechanism {
+        SaleMechanism::Dutch(auction) => {
+            let price = auction_price(e, auction.clone());
+            if !bid_auction(e, auction.clone(), buyer.clone()) {
+                return Err(PauletteError::BiddingFailed);
+            }
+            Ok(price)
+        }
+        SaleMechanism::FixedPrice(price) => {
+            transfer_to_treasury(e, buyer.clone(), price.clone())?;
+            Ok(price.clone())
+        }
+        // auctions run by the contract are only won through settle
+        SaleMechanism::English(_) | SaleMechanism::Sealed(_) => Err(PauletteError::WrongMechanism),
+    }
+}
+
+fn settle_english(
+    e: &Env,
+    id: BytesN<16>,
+    mut sale: Sale,
+    english: EnglishSale,
+) -> Result<(), PauletteError> {
+    let now = TimeStamp::current(e);
+    if english.deadline > now {
+        return Err(PauletteError::AuctionOpen);
+    }
+
+    let bid = match get_bid(e, id.clone()) {
+        Some(bid) => bid,
+        None => {
+            let deadline = now.add(english.params.duration.clone())?;
+            e.events()
+                .publish((symbol!("relist"), id.clone()), deadline.clone());
+            sale.mechanism = SaleMechanism::English(EnglishSale {
+                params: english.params,
+                deadline,
+            });
+            put_for_sale(e, id, sale);
+            return Ok(());
+        }
+    };
+
+    transfer_out(e, read_treasury(e)?, bid.amount.clone());
+    remove_bid(e, id.clone());
+    let expires = hand_over(e, id.clone(), sale, bid.bidder.clone(), bid.amount.clone())?;
+    e.events()
+        .publish((symbol!("settle"), id), (bid.bidder, bid.amount, expires));
+    Ok(())
+}
+
+// the highest revealed bid wins and pays the second highest one (or the reserve), deposits of bids that weren't revealed go to the treasury
+fn settle_sealed(
+    e: &Env,
+    id: BytesN<16>,
+    mut sale: Sale,
+    sealed: SealedSale,
+) -> Result<(), PauletteError> {
+    if sealed.reveal_end > TimeStamp::current(e) {
+        return Err(PauletteError::AuctionOpen);
+    }
+
+    let commitments = get_commitments(e, id.clone());
+    let mut winner: Option<Commitment> = None;
+    let mut price = sealed.params.reserve.clone();
+    for commitment in commitments.iter_unchecked().filter(|c| c.revealed) {
+        let beats = match &winner {
+            Some(best) => commitment.amount > best.amount,
+            None => true,
+        };
+
+        if beats {
+            if let Some(best) = winner.replace(commitment) {
+                price = best.amount;
+            }
+        } else if commitment.amount > price {
+            price = commitment.amount;
+        }
+    }
+
+    let treasury = read_treasury(e)?;
+    for commitment in commitments.iter_unchecked() {
+        if !commitment.revealed {
+            transfer_out(e, treasury.clone(), commitment.deposit);
+        } else if matches!(&winner, Some(best) if best.bidder == commitment.bidder) {
+            transfer_out(e, treasury.clone(), price.clone());
+            let change = commitment.deposit - price.clone();
+            if change > BigInt::zero(e) {
+                transfer_out(e, commitment.bidder, change);
+            }
+        } else {
+            transfer_out(e, commitment.bidder, commitment.deposit);
+        }
+    }
+    remove_commitments(e, id.clone());
+
+    let winner = match winner {
+        Some(winner) => winner,
+        None => {
+            let sealed = sealed_sale(e, sealed.params)?;
+            e.events()
+                .publish((symbol!("relist"), id.clone()), sealed.reveal_end.clone());
+            sale.mechanism = SaleMechanism::Sealed(sealed);
+            put_for_sale(e, id, sale);
+            return Ok(());
+        }
+    };
+
+    let expires = hand_over(e, id.clone(), sale, winner.bidder.clone(), price.clone())?;
+    e.events()
+        .publish((symbol!("settle"), id), (winner.bidder, price, expires));
+    Ok(())
+}
+
+// moves a sold office from ForSale to Bought, returning when it expires
+fn hand_over(
+    e: &Env,
+    id: BytesN<16>,
+    sale: Sale,
+    buyer: Identifier,
+    price: BigInt,
+) -> Result<TimeStamp, PauletteError> {
+    let expires = TimeStamp::current(e).add(sale.period.clone())?;
+
+    remove_for_sale(e, id.clone());
+    remove_id(e, Registry::ForSale, id.clone());
+    add_id(e, Registry::Held, id.clone());
+    add_id(e, Registry::Holdings(buyer.clone()), id.clone());
+    put_bought(
+        e,
+        id,
+        Office {
+            user: buyer,
+            expires: expires.clone(),
+            tax: sale.tax,
+            period: sale.period,
+            price: price.clone(),
+            valuation: price,
+        },
+    )?;
+    Ok(expires)
+}
+
+// the buyer pays with their own allowance, the office goes to the beneficiary
+fn buy_office(
+    e: &Env,
+    id: BytesN<16>,
+    buyer_id: Identifier,
+    beneficiary: Identifier,
+) -> Result<(), PauletteError> {
+    check_not_paused(e)?;
+    let sale = get_for_sale(e, id.clone())?;
+    let price = pay_sale(e, &sale.mechanism, &buyer_id)?;
+
+    let mechanism = sale.mechanism.clone();
+    let expires = hand_over(e, id.clone(), sale, beneficiary.clone(), price.clone())?;
+    e.events().publish(
+        (symbol!("buy"), id),
+        (buyer_id, beneficiary, price, expires, mechanism),
+    );
+    Ok(())
+}
+
+fn pay_office_tax(
+    e: &Env,
+    id: BytesN<16>,
+    payer: Identifier,
+    periods: u32,
+) -> Result<(), PauletteError> {
+    if periods == 0 {
+        return Err(PauletteError::NoPeriods);
+    }
+
+    let mut office = get_bought(e, id.clone())?;
+    let now = TimeStamp::current(e);
+    let tax = office_tax(e, &office);
+
+    // late payments are accepted with a surcharge until the grace window closes
+    let amount = if office.expires <= now {
+        if grace_end(e, &office)? <= now {
+            return Err(PauletteError::GracePeriodOver);
+        }
+        tax.clone() * BigInt::from_u32(e, periods) + get_penalty(e, &tax)
+    } else {
+        tax * BigInt::from_u32(e, periods)
+    };
+
+    office.expires = office.expires.add(office.period.clone().mul(periods)?)?;
+
+    if let Some(horizon) = get_max_prepay(e) {
+        if office.expires > now.add(horizon)? {
+            return Err(PauletteError::PrepayTooLong);
+        }
+    }
+
+    transfer_to_treasury(e, payer.clone(), amount.clone())?;
+    e.events().publish(
+        (symbol!("pay_tax"), id.clone()),
+        (payer, amount, office.expires.clone()),
+    );
+    put_bought(e, id, office)?;
+    Ok(())
+}
+
+fn get_office_price(e: &Env, id: BytesN<16>) -> Result<BigInt, PauletteError> {
+    let sale = get_for_sale(e, id.clone())?;
+    Ok(sale_price(e, &id, &sale.mechanism))
+}
+
+pub trait PauletteContractTrait {
+    /// Sets the admin, the Royal vault's token id, the dutch auction wasm and the default tax and period for offices
+    fn initialize(
+        e: Env,
+        admin: Identifier,
+        token_id: BytesN<32>,
+        auction_wasm: BytesN<32>,
+        tax: BigInt,
+        period: TimeStamp,
+    ) -> Result<(), PauletteError>;
+
+    /// Returns the version of the storage layout
+    fn version(e: Env) -> u32;
+
+    /// Rewrite the storage of the first release into the current layout (requires auth of its admin),
+    /// `ids` are the offices to carry over since that release didn't keep a registry. Offices can be
+    /// carried over across several calls, the last one passing `done`, and the rest of the contract
+    /// can't be used until then. Offices left out can still be carried over by later calls that don't
+    /// pass `done`, `auction_wasm` is only used by the first call
+    fn migrate(
+        e: Env,
+        admin: Auth,
+        auction_wasm: BytesN<32>,
+        ids: Vec<BytesN<16>>,
+        done: bool,
+    ) -> Result<(), PauletteError>;
+
+    /// Returns the nonce for the first admin
+    fn nonce(e: Env) -> Result<BigInt, PauletteError>;
+
+    /// Returns the nonce for the given identifier
+    fn nonce_of(e: Env, id: Identifier) -> BigInt;
+
+    /// Query the admin set
+    fn admins(e: Env) -> Result<AdminSet, PauletteError>;
+
+    /// Propose a new admin set, which has to accept to take over (requires admin auth)
+    fn propose_admin(e: Env, admins: Vec<Auth>, new_admin: AdminSet) -> Result<(), PauletteError>;
+
+    /// Make the proposed admin set the admin (requires auth of the proposed admins)
+    fn accept_admin(e: Env, new_admins: Vec<Auth>) -> Result<(), PauletteError>;
+
+    /// Grant a role to an identifier (requires admin auth)
+    fn grant_role(
+        e: Env,
+        admins: Vec<Auth>,
+        id: Identifier,
+        role: Role,
+    ) -> Result<(), PauletteError>;
+
+    /// Take a role away from an identifier (requires admin auth)
+    fn revoke_role(
+        e: Env,
+        admins: Vec<Auth>,
+        id: Identifier,
+        role: Role,
+    ) -> Result<(), PauletteError>;
+
+    /// Query whether an identifier holds a role
+    fn has_role(e: Env, id: Identifier, role: Role) -> bool;
+
+    /// Stop buying, creating and revoking offices, and paying taxes if `block_tax` is set (requires admin auth)
+    fn pause(e: Env, admins: Vec<Auth>, block_tax: bool) -> Result<(), PauletteError>;
+
+    /// Resume the contract, held offices expire later by how long taxes were blocked (requires admin auth)
+    fn unpause(e: Env, admins: Vec<Auth>) -> Result<(), PauletteError>;
+
+    /// Query whether the contract is paused
+    fn is_paused(e: Env) -> bool;
+
+    /// Set who receives taxes and the proceeds of auctions created from now on (requires admin auth)
+    fn set_treasury(e: Env, admins: Vec<Auth>, treasury: Identifier) -> Result<(), PauletteError>;
+
+    /// Call to buy an office for `beneficiary` (requires auth of the buyer, who pays)
+    fn buy(
+        e: Env,
+        id: BytesN<16>,
+        buyer: Auth,
+        beneficiary: Identifier,
+    ) -> Result<(), PauletteError>;
+
+    /// Call to buy an office for `beneficiary` unless it costs more than `max_price` (requires auth of the buyer, who pays)
+    fn buy_with_limit(
+        e: Env,
+        id: BytesN<16>,
+        buyer: Auth,
+        beneficiary: Identifier,
+        max_price: BigInt,
+    ) -> Result<(), PauletteError>;
+
+    /// Bid on an office sold through an english auction (requires bidder auth), the bid is held by the contract until someone outbids it
+    fn place_bid(e: Env, id: BytesN<16>, bidder: Auth, amount: BigInt)
+        -> Result<(), PauletteError>;
+
+    /// Commit to a sealed bid on an office (requires bidder auth), `hash` is the bid_hash of the bid and a secret salt and `deposit` is held by the contract until settlement
+    fn commit_bid(
+        e: Env,
+        id: BytesN<16>,
+        bidder: Auth,
+        hash: BytesN<32>,
+        deposit: BigInt,
+    ) -> Result<(), PauletteError>;
+
+    /// Reveal a sealed bid once the commit phase is over, bids that aren't revealed forfeit their deposit
+    fn reveal_bid(
+        e: Env,
+        id: BytesN<16>,
+        bidder: Identifier,
+        amount: BigInt,
+        salt: BytesN<32>,
+    ) -> Result<(), PauletteError>;
+
+    /// Give an office to the winner once its english or sealed-bid auction is over, or restart the auction if nobody bid
+    fn settle(e: Env, id: BytesN<16>) -> Result<(), PauletteError>;
+
+    /// Call to pay taxes for a given office (requires auth of the payer)
+    fn pay_tax(e: Env, id: BytesN<16>, payer: Auth) -> Result<(), PauletteError>;
+
+    /// Call to pay taxes for multiple periods of a given office at once (requires auth of the payer)
+    fn pay_tax_periods(
+        e: Env,
+        id: BytesN<16>,
+        payer: Auth,
+        periods: u32,
+    ) -> Result<(), PauletteError>;
+
+    /// Set how far in the future holders can prepay their offices (requires treasurer or admin auth)
+    fn set_max_prepay(e: Env, auths: Vec<Auth>, horizon: TimeStamp) -> Result<(), PauletteError>;
+
+    /// Hand a bought office over to another identifier (requires holder auth), keeps the expiry
+    fn transfer(e: Env, id: BytesN<16>, from: Auth, to: Identifier) -> Result<(), PauletteError>;
+
+    /// Designate the heir of a bought office (requires holder auth)
+    fn set_heir(
+        e: Env,
+        id: BytesN<16>,
+        holder: Auth,
+        heir: Identifier,
+    ) -> Result<(), PauletteError>;
+
+    /// Take over an expired office by paying the overdue tax (requires heir auth), only during the claim window
+    fn claim_inheritance(e: Env, id: BytesN<16>, heir: Auth) -> Result<(), PauletteError>;
+
+    /// Set how long heirs can claim an expired office before it can be revoked (requires admin auth)
+    fn set_claim_window(e: Env, admins: Vec<Auth>, window: TimeStamp) -> Result<(), PauletteError>;
+
+    /// Set the default flat tax for offices created from now on (requires treasurer or admin auth)
+    fn set_tax(e: Env, auths: Vec<Auth>, amount: BigInt) -> Result<(), PauletteError>;
+
+    /// Set the default period for offices created from now on (requires treasurer or admin auth)
+    fn set_period(e: Env, auths: Vec<Auth>, period: TimeStamp) -> Result<(), PauletteError>;
+
+    /// Set how long expired offices can still be paid for and the surcharge in basis points for doing so (requires treasurer or admin auth)
+    fn set_grace(
+        e: Env,
+        auths: Vec<Auth>,
+        window: TimeStamp,
+        penalty: u32,
+    ) -> Result<(), PauletteError>;
+
+    /// Query the price of a given office
+    fn get_price(e: Env, id: BytesN<16>) -> Result<BigInt, PauletteError>;
+
+    /// Query the status of a given office
+    fn get_office(e: Env, id: BytesN<16>) -> OfficeStatus;
+
+    /// Query the holder of a given office, if it has been bought
+    fn get_owner(e: Env, id: BytesN<16>) -> Option<Identifier>;
+
+    /// Query when a given office expires, if it has been bought
+    fn get_expiry(e: Env, id: BytesN<16>) -> Option<TimeStamp>;
+
+    /// Query whether a given office has been bought and has expired
+    fn is_expired(e: Env, id: BytesN<16>) -> bool;
+
+    /// List the ids of the offices held by the given identifier
+    fn offices_of(e: Env, holder: Identifier) -> Vec<BytesN<16>>;
+
+    /// Count the offices held by the given identifier
+    fn office_count(e: Env, holder: Identifier) -> u32;
+
+    /// List the ids of all the offices, `limit` ids starting from the `start`-th one
+    fn offices(e: Env, start: u32, limit: u32) -> Vec<BytesN<16>>;
+
+    /// List the ids of the offices that are for sale, `limit` ids starting from the `start`-th one
+    fn offices_for_sale(e: Env, start: u32, limit: u32) -> Vec<BytesN<16>>;
+
+    /// List the ids of the offices that have been bought, `limit` ids starting from the `start`-th one
+    fn offices_held(e: Env, start: u32, limit: u32) -> Vec<BytesN<16>>;
+
+    /// Query the tax to pay to keep a given office for a period
+    fn get_tax(e: Env, id: BytesN<16>) -> Result<BigInt, PauletteError>;
+
+    /// Declare the valuation of an office with a self-assessed tax (requires holder auth)
+    fn set_valuation(
+        e: Env,
+        id: BytesN<16>,
+        holder: Auth,
+        valuation: BigInt,
+    ) -> Result<(), PauletteError>;
+
+    /// Buy an office with a self-assessed tax at its declared valuation, paid to the holder, unless it's above `max_price` (requires buyer auth)
+    fn force_buy(
+        e: Env,
+        id: BytesN<16>,
+        buyer: Auth,
+        valuation: BigInt,
+        max_price: BigInt,
+    ) -> Result<(), PauletteError>;
+
+    /// Create a new office and put it up for sale (requires registrar or admin auth), `tax` and `period` default to the contract's ones
     fn new_office(
+        e: Env,
+        auths: Vec<Auth>,
+        id: BytesN<16>,
+        params: SaleParams,
+        tax: Option<Tax>,
+        period: Option<TimeStamp>,
+    ) -> Result<(), PauletteError>;
+
+    /// remove office from Bought, add it to ForSale with the given sale mechanism (requires collector or admin auth)
+    fn revoke(
+        e: Env,
+        auths: Vec<Auth>,
+        id: BytesN<16>,
+        params: SaleParams,
+        tax: Option<Tax>,
+        period: Option<TimeStamp>,
+    ) -> Result<(), PauletteError>;
+}
+
+pub struct PauletteContract;
+
+#[contractimpl]
+impl PauletteContractTrait for PauletteContract {
+    fn initialize(
+        e: Env,
+        admin: Identifier,
+        token_id: BytesN<32>,
+        auction_wasm: BytesN<32>,
+        tax: BigInt,
+        period: TimeStamp,
+    ) -> Result<(), PauletteError> {
+        if has_administrator(&e) {
+            return Err(PauletteError::AlreadyInitialized);
+        }
+
+        e.events().publish(
+            (symbol!("initialize"),),
+            (admin.clone(), token_id.clone(), tax.clone(), period.clone()),
+        );
+
+        let mut members = Vec::new(&e);
+        members.push_back(admin);
+        write_administrator(
+            &e,
+            AdminSet {
+                members,
+                threshold: 1,
+            },
+        )?;
+        put_token_id(&e, token_id);
+        put_auction_wasm(&e, auction_wasm);
+        put_default_tax(&e, tax);
+        put_default_period(&e, period);
+        put_version(&e, VERSION);
+        Ok(())
+    }
+
+    fn version(e: Env) -> u32 {
+        get_version(&e)
+    }
+
+    fn migrate(
         e: Env,
         admin: Auth,
+        auction_wasm: BytesN<32>,
+        ids: Vec<BytesN<16>>,
+        done: bool,
+    ) -> Result<(), PauletteError> {
+        let migrated = get_version(&e) >= VERSION;
+        if migrated && done {
+            return Err(PauletteError::AlreadyMigrated);
+        }
+        let args = (auction_wasm.clone(), ids.clone(), done).into_val(&e);
+
+        if migrated || e.data().has(DataKey::Migrating) {
+            // the admin is already stored in the current layout
+            check_admin(&e, &vec![&e, admin], symbol!("migrate"), args)?;
+        } else {
+            let admin_id = check_admin_v0(&e, &admin, symbol!("migrate"), args)?;
+
+            let mut members = Vec::new(&e);
+            members.push_back(admin_id);
+            write_administrator(
+                &e,
+                AdminSet {
+                    members,
+                    threshold: 1,
+                },
+            )?;
+            // offices used to last a week, and their auctions were deployed by hand
+            put_default_period(&e, TimeStamp(604800));
+            put_auction_wasm(&e, auction_wasm);
+            e.data().set(DataKey::Migrating, true);
+        }
+
+        for id in ids.iter_unchecked() {
+            // offices carried over by an earlier batch, or created since, are already in the current layout
+            if !has_id(&e, Registry::Offices, id.clone()) {
+                migrate_office_v0(&e, id)?;
+            }
+        }
+
+        if done {
+            e.data().remove(DataKey::Migrating);
+            e.events().publish((symbol!("migrate"),), VERSION);
+            put_version(&e, VERSION);
+        }
+        Ok(())
+    }
+
+    fn nonce(e: Env) -> Result<BigInt, PauletteError> {
+        Ok(read_nonce(&e, &read_first_administrator(&e)?))
+    }
+
+    fn nonce_of(e: Env, id: Identifier) -> BigInt {
+        read_nonce(&e, &id)
+    }
+
+    fn admins(e: Env) -> Result<AdminSet, PauletteError> {
+        read_administrator(&e)
+    }
+
+    fn propose_admin(e: Env, admins: Vec<Auth>, new_admin: AdminSet) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_admin(
+            &e,
+            &admins,
+            symbol!("prop_admin"),
+            (new_admin.clone(),).into_val(&e),
+        )?;
+
+        e.events().publish(
+            (symbol!("prop_admin"),),
+            (new_admin.members.clone(), new_admin.threshold),
+        );
+        put_pending_admin(&e, new_admin);
+        Ok(())
+    }
+
+    fn accept_admin(e: Env, new_admins: Vec<Auth>) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        let new_admin = get_pending_admin(&e)?;
+        check_signers(
+            &e,
+            &new_admin,
+            &new_admins,
+            PauletteError::NotPendingAdmin,
+            symbol!("acc_admin"),
+            (new_admin.clone(),).into_val(&e),
+        )?;
+
+        e.events().publish(
+            (symbol!("acc_admin"),),
+            (new_admin.members.clone(), new_admin.threshold),
+        );
+        remove_pending_admin(&e);
+        write_administrator(&e, new_admin)
+    }
+
+    fn grant_role(
+        e: Env,
+        admins: Vec<Auth>,
+        id: Identifier,
+        role: Role,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_admin(
+            &e,
+            &admins,
+            symbol!("grant_role"),
+            (id.clone(), role).into_val(&e),
+        )?;
+
+        let mut roles = read_roles(&e, &id);
+        if !roles.iter_unchecked().any(|other| other == role) {
+            roles.push_back(role);
+        }
+
+        e.events()
+            .publish((symbol!("grant_role"), id.clone()), role);
+        write_roles(&e, id, roles);
+        Ok(())
+    }
+
+    fn revoke_role(
+        e: Env,
+        admins: Vec<Auth>,
+        id: Identifier,
+        role: Role,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_admin(
+            &e,
+            &admins,
+            symbol!("rev_role"),
+            (id.clone(), role).into_val(&e),
+        )?;
+
+        let mut roles = read_roles(&e, &id);
+        if let Some(i) = roles.iter_unchecked().position(|other| other == role) {
+            roles.remove(i as u32);
+        }
+
+        e.events().publish((symbol!("rev_role"), id.clone()), role);
+        write_roles(&e, id, roles);
+        Ok(())
+    }
+
+    fn has_role(e: Env, id: Identifier, role: Role) -> bool {
+        holds_role(&e, &id, role)
+    }
+
+    fn pause(e: Env, admins: Vec<Auth>, block_tax: bool) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_admin(&e, &admins, symbol!("pause"), (block_tax,).into_val(&e))?;
+        check_not_paused(&e)?;
+
+        e.events().publish((symbol!("pause"),), block_tax);
+        put_pause(
+            &e,
+            Pause {
+                since: TimeStamp::current(&e),
+                block_tax,
+            },
+        );
+        Ok(())
+    }
+
+    fn unpause(e: Env, admins: Vec<Auth>) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_admin(&e, &admins, symbol!("unpause"), Vec::new(&e))?;
+        let pause = get_pause(&e).ok_or(PauletteError::NotPaused)?;
+
+        let duration = TimeStamp(TimeStamp::current(&e).0 - pause.since.0);
+        e.events().publish((symbol!("unpause"),), duration.clone());
+        if pause.block_tax {
+            put_paused_for(&e, get_paused_for(&e).add(duration)?);
+        }
+        remove_pause(&e);
+        Ok(())
+    }
+
+    fn is_paused(e: Env) -> bool {
+        get_pause(&e).is_some()
+    }
+
+    fn set_treasury(e: Env, admins: Vec<Auth>, treasury: Identifier) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_admin(
+            &e,
+            &admins,
+            symbol!("treasury"),
+            (treasury.clone(),).into_val(&e),
+        )?;
+
+        e.events().publish((symbol!("treasury"),), treasury.clone());
+        write_treasury(&e, treasury);
+        Ok(())
+    }
+
+    fn buy(
+        e: Env,
+        id: BytesN<16>,
+        buyer: Auth,
+        beneficiary: Identifier,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        verify_auth(
+            &e,
+            &buyer,
+            symbol!("buy"),
+            (id.clone(), beneficiary.clone()).into_val(&e),
+        )?;
+        buy_office(&e, id, buyer.sig.identifier(&e), beneficiary)
+    }
+
+    fn buy_with_limit(
+        e: Env,
+        id: BytesN<16>,
+        buyer: Auth,
+        beneficiary: Identifier,
+        max_price: BigInt,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        verify_auth(
+            &e,
+            &buyer,
+            symbol!("buy_limit"),
+            (id.clone(), beneficiary.clone(), max_price.clone()).into_val(&e),
+        )?;
+
+        if get_office_price(&e, id.clone())? > max_price {
+            return Err(PauletteError::PriceAboveLimit);
+        }
+        buy_office(&e, id, buyer.sig.identifier(&e), beneficiary)
+    }
+
+    fn place_bid(
+        e: Env,
+        id: BytesN<16>,
+        bidder: Auth,
+        amount: BigInt,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_not_paused(&e)?;
+        verify_auth(
+            &e,
+            &bidder,
+            symbol!("bid"),
+            (id.clone(), amount.clone()).into_val(&e),
+        )?;
+        let bidder = bidder.sig.identifier(&e);
+
+        let sale = get_for_sale(&e, id.clone())?;
+        let EnglishSale { params, deadline } = match sale.mechanism {
+            SaleMechanism::English(english) => english,
+            _ => return Err(PauletteError::WrongMechanism),
+        };
+
+        if deadline <= TimeStamp::current(&e) {
+            return Err(PauletteError::AuctionClosed);
+        }
+
+        let highest = get_bid(&e, id.clone());
+        let min_bid = match &highest {
+            Some(bid) => bid.amount.clone() + params.increment,
+            None => params.reserve,
+        };
+        if amount < min_bid {
+            return Err(PauletteError::BidTooLow);
+        }
+
+        escrow(&e, bidder.clone(), amount.clone());
+        if let Some(outbid) = highest {
+            transfer_out(&e, outbid.bidder, outbid.amount);
+        }
+
+        e.events().publish(
+            (symbol!("bid"), id.clone()),
+            (bidder.clone(), amount.clone()),
+        );
+        put_bid(&e, id, Bid { bidder, amount });
+        Ok(())
+    }
+
+    fn commit_bid(
+        e: Env,
+        id: BytesN<16>,
+        bidder: Auth,
+        hash: BytesN<32>,
+        deposit: BigInt,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_not_paused(&e)?;
+        verify_auth(
+            &e,
+            &bidder,
+            symbol!("commit"),
+            (id.clone(), hash.clone(), deposit.clone()).into_val(&e),
+        )?;
+        let bidder = bidder.sig.identifier(&e);
+
+        let sale = get_for_sale(&e, id.clone())?;
+        let sealed = match sale.mechanism {
+            SaleMechanism::Sealed(sealed) => sealed,
+            _ => return Err(PauletteError::WrongMechanism),
+        };
+
+        if sealed.commit_end <= TimeStamp::current(&e) {
+            return Err(PauletteError::AuctionClosed);
+        }
+
+        if deposit < sealed.params.reserve {
+            return Err(PauletteError::BidTooLow);
+        }
+
+        let mut commitments = get_commitments(&e, id.clone());
+        if commitments.iter_unchecked().any(|c| c.bidder == bidder) {
+            return Err(PauletteError::AlreadyCommitted);
+        }
+
+        if commitments.len() >= MAX_COMMITMENTS {
+            return Err(PauletteError::TooManyBids);
+        }
+
+        escrow(&e, bidder.clone(), deposit.clone());
+        e.events().publish(
+            (symbol!("commit"), id.clone()),
+            (bidder.clone(), deposit.clone()),
+        );
+        commitments.push_back(Commitment {
+            bidder,
+            hash,
+            deposit,
+            revealed: false,
+            amount: BigInt::zero(&e),
+        });
+        put_commitments(&e, id, commitments);
+        Ok(())
+    }
+
+    fn reveal_bid(
+        e: Env,
+        id: BytesN<16>,
+        bidder: Identifier,
+        amount: BigInt,
+        salt: BytesN<32>,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        let sale = get_for_sale(&e, id.clone())?;
+        let sealed = match sale.mechanism {
+            SaleMechanism::Sealed(sealed) => sealed,
+            _ => return Err(PauletteError::WrongMechanism),
+        };
+
+        let now = TimeStamp::current(&e);
+        if sealed.commit_end > now || sealed.reveal_end <= now {
+            return Err(PauletteError::NotRevealPhase);
+        }
+
+        let mut commitments = get_commitments(&e, id.clone());
+        let i = commitments
+            .iter_unchecked()
+            .position(|c| c.bidder == bidder)
+            .ok_or(PauletteError::NoCommitment)? as u32;
+        let mut commitment = commitments.get_unchecked(i).unwrap();
+
+        if bid_hash(&e, &amount, &salt) != commitment.hash {
+            return Err(PauletteError::HashMismatch);
+        }
+
+        if amount < sealed.params.reserve {
+            return Err(PauletteError::BidTooLow);
+        }
+
+        if amount > commitment.deposit {
+            return Err(PauletteError::DepositTooLow);
+        }
+
+        e.events()
+            .publish((symbol!("reveal"), id.clone()), (bidder, amount.clone()));
+        commitment.revealed = true;
+        commitment.amount = amount;
+        commitments.set(i, commitment);
+        put_commitments(&e, id, commitments);
+        Ok(())
+    }
+
+    fn settle(e: Env, id: BytesN<16>) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_not_paused(&e)?;
+        let sale = get_for_sale(&e, id.clone())?;
+        match sale.mechanism.clone() {
+            SaleMechanism::English(english) => settle_english(&e, id, sale, english),
+            SaleMechanism::Sealed(sealed) => settle_sealed(&e, id, sale, sealed),
+            _ => Err(PauletteError::WrongMechanism),
+        }
+    }
+
+    // the contract doesn't care if its the user who pays the office, just that someone is.
+    fn pay_tax(e: Env, id: BytesN<16>, payer: Auth) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_tax_not_paused(&e)?;
+        // the tax is pulled from the payer's allowance, which may have been given for something else
+        verify_auth(&e, &payer, symbol!("pay_tax"), (id.clone(),).into_val(&e))?;
+        pay_office_tax(&e, id, payer.sig.identifier(&e), 1)
+    }
+
+    fn pay_tax_periods(
+        e: Env,
+        id: BytesN<16>,
+        payer: Auth,
+        periods: u32,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_tax_not_paused(&e)?;
+        verify_auth(
+            &e,
+            &payer,
+            symbol!("pay_taxes"),
+            (id.clone(), periods).into_val(&e),
+        )?;
+        pay_office_tax(&e, id, payer.sig.identifier(&e), periods)
+    }
+
+    fn set_max_prepay(e: Env, auths: Vec<Auth>, horizon: TimeStamp) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_role(
+            &e,
+            Role::Treasurer,
+            &auths,
+            symbol!("max_prepay"),
+            (horizon.clone(),).into_val(&e),
+        )?;
+
+        put_max_prepay(&e, horizon);
+        Ok(())
+    }
+
+    fn transfer(e: Env, id: BytesN<16>, from: Auth, to: Identifier) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        let mut office = get_bought(&e, id.clone())?;
+
+        check_holder(&e, &from.sig, &office)?;
+        verify_auth(
+            &e,
+            &from,
+            symbol!("transfer"),
+            (id.clone(), to.clone()).into_val(&e),
+        )?;
+
+        e.events().publish(
+            (symbol!("transfer"), id.clone()),
+            (office.user.clone(), to.clone()),
+        );
+
+        // the heir was chosen by the previous holder
+        remove_heir(&e, id.clone());
+        move_holding(&e, id.clone(), office.user, to.clone());
+        office.user = to;
+        put_bought(&e, id, office)?;
+        Ok(())
+    }
+
+    fn set_heir(
+        e: Env,
+        id: BytesN<16>,
+        holder: Auth,
+        heir: Identifier,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        let office = get_bought(&e, id.clone())?;
+
+        check_holder(&e, &holder.sig, &office)?;
+        verify_auth(
+            &e,
+            &holder,
+            symbol!("set_heir"),
+            (id.clone(), heir.clone()).into_val(&e),
+        )?;
+
+        e.events()
+            .publish((symbol!("set_heir"), id.clone()), heir.clone());
+        put_heir(&e, id, heir);
+        Ok(())
+    }
+
+    fn claim_inheritance(e: Env, id: BytesN<16>, heir: Auth) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        let mut office = get_bought(&e, id.clone())?;
+
+        let heir_id = check_heir(&e, &heir.sig, id.clone())?;
+        verify_auth(&e, &heir, symbol!("inherit"), (id.clone(),).into_val(&e))?;
+
+        let now = TimeStamp::current(&e);
+        if office.expires > now {
+            return Err(PauletteError::NotExpired);
+        }
+
+        // the holder can still pay late during the grace window
+        let grace_end = grace_end(&e, &office)?;
+        if grace_end > now {
+            return Err(PauletteError::InGracePeriod);
+        }
+
+        if grace_end.add(get_claim_window(&e))? <= now {
+            return Err(PauletteError::ClaimWindowClosed);
+        }
+
+        // the heir pays the overdue tax to take over the office
+        let tax = office_tax(&e, &office);
+        let amount = tax.clone() + get_penalty(&e, &tax);
+        transfer_to_treasury(&e, heir_id.clone(), amount.clone())?;
+
+        remove_heir(&e, id.clone());
+        move_holding(&e, id.clone(), office.user, heir_id.clone());
+        office.user = heir_id.clone();
+        // the office expired before the claim window opened, the period paid starts now
+        office.expires = now.add(office.period.clone())?;
+
+        e.events().publish(
+            (symbol!("inherit"), id.clone()),
+            (heir_id, amount, office.expires.clone()),
+        );
+        put_bought(&e, id, office)?;
+        Ok(())
+    }
+
+    fn set_claim_window(e: Env, admins: Vec<Auth>, window: TimeStamp) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_admin(
+            &e,
+            &admins,
+            symbol!("claim_win"),
+            (window.clone(),).into_val(&e),
+        )?;
+
+        put_claim_window(&e, window);
+        Ok(())
+    }
+
+    fn set_tax(e: Env, auths: Vec<Auth>, amount: BigInt) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_role(
+            &e,
+            Role::Treasurer,
+            &auths,
+            symbol!("set_tax"),
+            (amount.clone(),).into_val(&e),
+        )?;
+
+        put_default_tax(&e, amount);
+        Ok(())
+    }
+
+    fn set_period(e: Env, auths: Vec<Auth>, period: TimeStamp) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_role(
+            &e,
+            Role::Treasurer,
+            &auths,
+            symbol!("set_period"),
+            (period.clone(),).into_val(&e),
+        )?;
+
+        put_default_period(&e, period);
+        Ok(())
+    }
+
+    fn set_grace(
+        e: Env,
+        auths: Vec<Auth>,
+        window: TimeStamp,
+        penalty: u32,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_role(
+            &e,
+            Role::Treasurer,
+            &auths,
+            symbol!("set_grace"),
+            (window.clone(), penalty).into_val(&e),
+        )?;
+
+        put_grace(&e, window, penalty);
+        Ok(())
+    }
+
+    fn new_office(
+        e: Env,
+        auths: Vec<Auth>,
         id: BytesN<16>,
-        auction: BytesN<32>,
-        price: BigInt,
-        min_price: BigInt,
-        slope: BigInt,
-    ) {
-        check_admin(&e, &admin.sig);
-        verify_and_consume_nonce(&e, &admin.sig, &admin.nonce);
+        params: SaleParams,
+        tax: Option<Tax>,
+        period: Option<TimeStamp>,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_role(
+            &e,
+            Role::Registrar,
+            &auths,
+            symbol!("new_office"),
+            (id.clone(), params.clone(), tax.clone(), period.clone()).into_val(&e),
+        )?;
+        check_not_paused(&e)?;
 
         if e.data().has(DataKey::ForSale(id.clone())) {
-            panic!("id already exists")
+            return Err(PauletteError::IdAlreadyExists);
         }
 
         if e.data().has(DataKey::Bought(id.clone())) {
-            panic!("id already exists")
+            return Err(PauletteError::IdAlreadyExists);
         }
 
-        make_new_office(&e, id, auction, price, min_price, slope);
+        add_id(&e, Registry::Offices, id.clone());
+        let mechanism = make_new_office(&e, id.clone(), params, tax, period)?;
+
+        let price = sale_price(&e, &id, &mechanism);
+        e.events()
+            .publish((symbol!("new_office"), id), (mechanism, price));
+        Ok(())
     }
 
-    fn get_price(e: Env, id: BytesN<16>) -> BigInt {
+    fn get_price(e: Env, id: BytesN<16>) -> Result<BigInt, PauletteError> {
+        check_not_migrating(&e)?;
         get_office_price(&e, id)
     }
 
+    fn get_office(e: Env, id: BytesN<16>) -> OfficeStatus {
+        if let Ok(office) = get_bought(&e, id.clone()) {
+            let expired = office.expires <= TimeStamp::current(&e);
+            let tenure = Tenure {
+                user: office.user,
+                expires: office.expires,
+            };
+
+            return if expired {
+                OfficeStatus::Expired(tenure)
+            } else {
+                OfficeStatus::Held(tenure)
+            };
+        }
+
+        match get_for_sale(&e, id.clone()) {
+            Ok(sale) => OfficeStatus::ForSale(Listing {
+                price: sale_price(&e, &id, &sale.mechanism),
+                mechanism: sale.mechanism,
+            }),
+            Err(_) => OfficeStatus::Unknown,
+        }
+    }
+
+    fn get_owner(e: Env, id: BytesN<16>) -> Option<Identifier> {
+        get_bought(&e, id).ok().map(|office| office.user)
+    }
+
+    fn get_expiry(e: Env, id: BytesN<16>) -> Option<TimeStamp> {
+        get_bought(&e, id).ok().map(|office| office.expires)
+    }
+
+    fn is_expired(e: Env, id: BytesN<16>) -> bool {
+        match get_bought(&e, id) {
+            Ok(office) => office.expires <= TimeStamp::current(&e),
+            Err(_) => false,
+        }
+    }
+
+    fn offices_of(e: Env, holder: Identifier) -> Vec<BytesN<16>> {
+        let holdings = Registry::Holdings(holder);
+        page_ids(&e, holdings.clone(), 0, registry_len(&e, holdings))
+    }
+
+    fn office_count(e: Env, holder: Identifier) -> u32 {
+        registry_len(&e, Registry::Holdings(holder))
+    }
+
+    fn offices(e: Env, start: u32, limit: u32) -> Vec<BytesN<16>> {
+        page_ids(&e, Registry::Offices, start, limit)
+    }
+
+    fn offices_for_sale(e: Env, start: u32, limit: u32) -> Vec<BytesN<16>> {
+        page_ids(&e, Registry::ForSale, start, limit)
+    }
+
+    fn offices_held(e: Env, start: u32, limit: u32) -> Vec<BytesN<16>> {
+        page_ids(&e, Registry::Held, start, limit)
+    }
+
+    fn get_tax(e: Env, id: BytesN<16>) -> Result<BigInt, PauletteError> {
+        check_not_migrating(&e)?;
+        if let Ok(office) = get_bought(&e, id.clone()) {
+            return Ok(office_tax(&e, &office));
+        }
+
+        // estimated on the price the office would be bought for right now
+        let sale = get_for_sale(&e, id.clone()).map_err(|_| PauletteError::OfficeNotFound)?;
+        let price = sale_price(&e, &id, &sale.mechanism);
+        Ok(match sale.tax {
+            Tax::Flat(amount) => amount,
+            Tax::SelfAssessed(rate) | Tax::Proportional(rate) => basis_points(&e, &price, rate),
+        })
+    }
+
+    fn set_valuation(
+        e: Env,
+        id: BytesN<16>,
+        holder: Auth,
+        valuation: BigInt,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        let mut office = get_bought(&e, id.clone())?;
+
+        check_holder(&e, &holder.sig, &office)?;
+        verify_auth(
+            &e,
+            &holder,
+            symbol!("valuation"),
+            (id.clone(), valuation.clone()).into_val(&e),
+        )?;
+
+        if !matches!(office.tax, Tax::SelfAssessed(_)) {
+            return Err(PauletteError::NotSelfAssessed);
+        }
+
+        revalue(&e, &mut office, valuation.clone())?;
+        e.events().publish(
+            (symbol!("valuation"), id.clone()),
+            (valuation, office.expires.clone()),
+        );
+        put_bought(&e, id, office)?;
+        Ok(())
+    }
+
+    fn force_buy(
+        e: Env,
+        id: BytesN<16>,
+        buyer: Auth,
+        valuation: BigInt,
+        max_price: BigInt,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_not_paused(&e)?;
+        let mut office = get_bought(&e, id.clone())?;
+
+        verify_auth(
+            &e,
+            &buyer,
+            symbol!("force_buy"),
+            (id.clone(), valuation.clone(), max_price.clone()).into_val(&e),
+        )?;
+
+        if !matches!(office.tax, Tax::SelfAssessed(_)) {
+            return Err(PauletteError::NotSelfAssessed);
+        }
+
+        // the holder could raise the valuation right before the purchase
+        if office.valuation > max_price {
+            return Err(PauletteError::PriceAboveLimit);
+        }
+
+        // offices past their grace window are waiting to be revoked
+        if grace_end(&e, &office)? <= TimeStamp::current(&e) {
+            return Err(PauletteError::GracePeriodOver);
+        }
+
+        let buyer_id = buyer.sig.identifier(&e);
+        transfer_from(
+            &e,
+            buyer_id.clone(),
+            office.user.clone(),
+            office.valuation.clone(),
+        );
+
+        e.events().publish(
+            (symbol!("force_buy"), id.clone()),
+            (
+                buyer_id.clone(),
+                office.user.clone(),
+                office.valuation.clone(),
+            ),
+        );
+
+        // taxes already paid stay with the office
+        remove_heir(&e, id.clone());
+        move_holding(&e, id.clone(), office.user, buyer_id.clone());
+        office.user = buyer_id;
+        office.price = office.valuation.clone();
+        revalue(&e, &mut office, valuation)?;
+        put_bought(&e, id, office)?;
+        Ok(())
+    }
+
     fn revoke(
         e: Env,
-        admin: Auth,
+        auths: Vec<Auth>,
         id: BytesN<16>,
-        auction: BytesN<32>,
-        price: BigInt,
-        min_price: BigInt,
-        slope: BigInt,
-    ) {
-        check_admin(&e, &admin.sig);
-        verify_and_consume_nonce(&e, &admin.sig, &admin.nonce);
+        params: SaleParams,
+        tax: Option<Tax>,
+        period: Option<TimeStamp>,
+    ) -> Result<(), PauletteError> {
+        check_not_migrating(&e)?;
+        check_role(
+            &e,
+            Role::Collector,
+            &auths,
+            symbol!("revoke"),
+            (id.clone(), params.clone(), tax.clone(), period.clone()).into_val(&e),
+        )?;
+        check_not_paused(&e)?;
+
+        let office = get_bought(&e, id.clone())?;
+
+        let now = TimeStamp::current(&e);
+        if office.expires > now {
+            return Err(PauletteError::NotExpired);
+        }
 
-        let office = get_bought(&e, id.clone());
+        let grace_end = grace_end(&e, &office)?;
+        if grace_end > now {
+            return Err(PauletteError::InGracePeriod);
+        }
 
-        if office.expires > TimeStamp::current(&e) {
-            panic!("office is not expired yet");
+        // the heir has the exclusive right to claim the office first
+        if get_heir(&e, id.clone()).is_some() && grace_end.add(get_claim_window(&e))? > now {
+            return Err(PauletteError::ClaimWindowOpen);
         }
 
+        remove_heir(&e, id.clone());
         remove_bought(&e, id.clone());
-        make_new_office(&e, id, auction, price, min_price, slope);
+        remove_id(&e, Registry::Held, id.clone());
+        remove_id(&e, Registry::Holdings(office.user.clone()), id.clone());
+        let mechanism = make_new_office(&e, id.clone(), params, tax, period)?;
+
+        let price = sale_price(&e, &id, &mechanism);
+        e.events()
+            .publish((symbol!("revoke"), id), (office.user, mechanism, price));
+        Ok(())
     }
 }
@@ -1,14 +1,20 @@
 #![cfg(test)]
 
-use crate::auction;
 use crate::testutils::{register_test_contract as register_paulette, PauletteContract};
 use crate::token::{self, TokenMetadata};
+use crate::{
+    auction, bid_hash, AdminSet, Auth, DataKey, DutchAuction, EnglishAuction, Listing,
+    OfficeStatus, OfficeV0, Role, SaleMechanism, SaleParams, SealedAuction, Tax, Tenure, TimeStamp,
+};
+use ed25519_dalek::Keypair;
 use rand::{thread_rng, RngCore};
-use soroban_auth::{Identifier, Signature};
-use soroban_sdk::bigint;
+use soroban_auth::testutils::ed25519::{generate, sign};
+use soroban_auth::{Ed25519Signature, Identifier, Signature};
+use soroban_sdk::{bigint, vec};
 use soroban_sdk::{
-    testutils::{Accounts, Ledger, LedgerInfo},
-    AccountId, BigInt, BytesN, Env, IntoVal,
+    symbol,
+    testutils::{Accounts, Events, Ledger, LedgerInfo},
+    AccountId, BigInt, BytesN, Env, IntoVal, RawVal, Symbol, Vec,
 };
 
 fn generate_contract_id() -> [u8; 32] {
@@ -43,14 +49,114 @@ fn create_paulette_contract(
     admin: &AccountId,
     token_id: &[u8; 32],
     tax: BigInt,
+    period: u64,
 ) -> ([u8; 32], PauletteContract) {
     let id = generate_contract_id();
     register_paulette(e, &id);
     let paulette = PauletteContract::new(e, &id);
-    paulette.initialize(&Identifier::Account(admin.clone()), token_id, tax);
+    paulette.initialize(
+        &Identifier::Account(admin.clone()),
+        token_id,
+        &e.install_contract_wasm(auction::WASM),
+        tax,
+        TimeStamp(period),
+    );
     (id, paulette)
 }
 
+fn set_timestamp(e: &Env, timestamp: u64) {
+    e.ledger().set(LedgerInfo {
+        timestamp,
+        protocol_version: 1,
+        sequence_number: 10,
+        network_passphrase: Default::default(),
+        base_reserve: 10,
+    });
+}
+
+fn mint(e: &Env, token: &token::Client, token_admin: &AccountId, to: &AccountId, amount: u32) {
+    token.with_source_account(token_admin).mint(
+        &Signature::Invoker,
+        &BigInt::zero(e),
+        &Identifier::Account(to.clone()),
+        &BigInt::from_u32(e, amount),
+    );
+}
+
+fn register_auction(e: &Env) -> BytesN<32> {
+    let auction_id = BytesN::from_array(e, &generate_contract_id());
+    e.register_contract_wasm(&auction_id, auction::WASM);
+    auction_id
+}
+
+// the dutch auction the office is currently sold with
+fn auction_of(paulette: &PauletteContract, office_id: BytesN<16>) -> BytesN<32> {
+    match paulette.get_office(office_id) {
+        OfficeStatus::ForSale(Listing {
+            mechanism: SaleMechanism::Dutch(auction),
+            ..
+        }) => auction,
+        _ => panic!("office isn't for sale"),
+    }
+}
+
+// events published by the given contract only, leaving out the token's and auctions' ones
+fn contract_events(e: &Env, contract_id: &BytesN<32>) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)> {
+    let mut events = Vec::new(e);
+    for event in e.events().all().iter_unchecked() {
+        if &event.0 == contract_id {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+fn event(
+    e: &Env,
+    contract_id: &BytesN<32>,
+    topics: impl IntoVal<Env, Vec<RawVal>>,
+    data: impl IntoVal<Env, RawVal>,
+) -> (BytesN<32>, Vec<RawVal>, RawVal) {
+    (contract_id.clone(), topics.into_val(e), data.into_val(e))
+}
+
+// creates a new office as `admin` and buys it as `buyer` at the auction's current price
+fn buy_new_office(
+    e: &Env,
+    paulette: &PauletteContract,
+    token: &token::Client,
+    admin: &AccountId,
+    buyer: &AccountId,
+) -> BytesN<16> {
+    let office_id = BytesN::from_array(e, &generate_office_id());
+    paulette.new_office(
+        admin.clone(),
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(e, 5),
+            min_price: bigint!(e, 1),
+            slope: bigint!(e, 900),
+        }),
+        None,
+        None,
+    );
+    let auction_id = auction_of(paulette, office_id.clone());
+
+    token.with_source_account(buyer).approve(
+        &Signature::Invoker,
+        &BigInt::zero(e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(
+        office_id.clone(),
+        buyer.clone(),
+        Identifier::Account(buyer.clone()),
+    );
+
+    office_id
+}
+
 #[test]
 fn test_sequence() {
     let e: Env = Default::default();
@@ -63,13 +169,9 @@ fn test_sequence() {
 
     let (contract1, usdc_token) = create_token_contract(&e, &admin1); // registered and initialized the usdc token contract
     let (contract_paulette, paulette) =
-        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20)); // registered and initialized the paulette token contract, with usdc as paulette token
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800); // registered and initialized the paulette token contract, with usdc as paulette token
     let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette)); // the id of the paulette
 
-    let auction_id = BytesN::from_array(&e, &generate_contract_id());
-    let auction_contract_id = Identifier::Contract(auction_id.clone());
-    e.register_contract_wasm(&auction_id, auction::WASM);
-
     // minting 1000 usdc to user1
     usdc_token.with_source_account(&admin1).mint(
         &Signature::Invoker,
@@ -99,11 +201,15 @@ fn test_sequence() {
     paulette.new_office(
         user1.clone(),
         office_id.clone(),
-        auction_id,
-        bigint!(&e, 5),
-        bigint!(&e, 1),
-        bigint!(&e, 900),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        None,
+        None,
     );
+    let auction_contract_id = Identifier::Contract(auction_of(&paulette, office_id.clone()));
 
     e.ledger().set(LedgerInfo {
         timestamp: 1666360875,
@@ -123,7 +229,7 @@ fn test_sequence() {
         &paulette.get_price(office_id.clone()),
     );
 
-    paulette.buy(office_id.clone(), user2_id.clone());
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
 
     assert_eq!(usdc_token.balance(&user1_id), 1003);
 
@@ -142,7 +248,7 @@ fn test_sequence() {
         &bigint!(&e, 20),
     );
 
-    paulette.pay_tax(office_id.clone(), user2_id);
+    paulette.pay_tax(office_id.clone(), user2.clone());
     assert_eq!(usdc_token.balance(&user1_id), 1023);
 
     e.ledger().set(LedgerInfo {
@@ -153,24 +259,23 @@ fn test_sequence() {
         base_reserve: 10,
     });
 
-    let auction_1_id = BytesN::from_array(&e, &generate_contract_id());
-    let _auction_1_contract_id = Identifier::Contract(auction_1_id.clone());
-    e.register_contract_wasm(&auction_1_id, auction::WASM);
-
     paulette.revoke(
         user1,
         office_id.clone(),
-        auction_1_id,
-        bigint!(&e, 50),
-        bigint!(&e, 5),
-        bigint!(&e, 1800),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
     );
 
     assert_eq!(paulette.get_price(office_id), 50);
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "Status(ContractError(5))")] // PauletteError::NotExpired
 fn test_invalid_revoke() {
     let e: Env = Default::default();
     let admin1 = e.accounts().generate(); // generating the usdc admin
@@ -182,11 +287,7 @@ fn test_invalid_revoke() {
 
     let (contract1, usdc_token) = create_token_contract(&e, &admin1); // registered and initialized the usdc token contract
     let (_contract_paulette, paulette) =
-        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20)); // registered and initialized the paulette token contract, with usdc as paulette token
-
-    let auction_id = BytesN::from_array(&e, &generate_contract_id());
-    let auction_contract_id = Identifier::Contract(auction_id.clone());
-    e.register_contract_wasm(&auction_id, auction::WASM);
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800); // registered and initialized the paulette token contract, with usdc as paulette token
 
     // minting 1000 usdc to user1
     usdc_token.with_source_account(&admin1).mint(
@@ -217,11 +318,15 @@ fn test_invalid_revoke() {
     paulette.new_office(
         user1.clone(),
         office_id.clone(),
-        auction_id.clone(),
-        bigint!(&e, 5),
-        bigint!(&e, 1),
-        bigint!(&e, 900),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        None,
+        None,
     );
+    let auction_contract_id = Identifier::Contract(auction_of(&paulette, office_id.clone()));
 
     e.ledger().set(LedgerInfo {
         timestamp: 1666360875,
@@ -241,7 +346,7 @@ fn test_invalid_revoke() {
         &paulette.get_price(office_id.clone()),
     );
 
-    paulette.buy(office_id.clone(), user2_id);
+    paulette.buy(office_id.clone(), user2.clone(), user2_id);
 
     assert_eq!(usdc_token.balance(&user1_id), 1003);
 
@@ -257,15 +362,18 @@ fn test_invalid_revoke() {
     paulette.revoke(
         user1,
         office_id,
-        auction_id,
-        bigint!(&e, 1),
-        bigint!(&e, 1),
-        bigint!(&e, 1),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 1),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 1),
+        }),
+        None,
+        None,
     );
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "Status(ContractError(6))")] // PauletteError::NotAuthorized
 fn test_invalid_admin() {
     let e: Env = Default::default();
     let admin1 = e.accounts().generate(); // generating the usdc admin
@@ -277,8 +385,7 @@ fn test_invalid_admin() {
 
     let (contract1, usdc_token) = create_token_contract(&e, &admin1); // registered and initialized the usdc token contract
     let (_contract_paulette, paulette) =
-        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20)); // registered and initialized the paulette token contract, with usdc as paulette token
-    let auction_id = BytesN::from_array(&e, &generate_contract_id());
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800); // registered and initialized the paulette token contract, with usdc as paulette token
 
     // minting 1000 usdc to user1
     usdc_token.with_source_account(&admin1).mint(
@@ -309,9 +416,2745 @@ fn test_invalid_admin() {
     paulette.new_office(
         user2, // not the admin
         office_id,
-        auction_id,
-        bigint!(&e, 5),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        None,
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(1))")] // PauletteError::AlreadyInitialized
+fn test_double_initialize() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+    let user1 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    paulette.initialize(
+        &Identifier::Account(admin1),
+        &contract1,
         bigint!(&e, 1),
-        bigint!(&e, 900),
+        TimeStamp(604800),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(9))")] // PauletteError::NotForSale
+fn test_buy_unknown_office() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    // no office has been created with this id
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.buy(office_id, user2.clone(), Identifier::Account(user2));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(10))")] // PauletteError::NotBought
+fn test_pay_tax_unknown_office() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    // no office has been bought with this id
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.pay_tax(office_id, user2.clone());
+}
+
+#[test]
+fn test_transfer() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    // user2 hands the office to user3, who can then hand it back
+    paulette.transfer(office_id.clone(), user2.clone(), user3_id.clone());
+    paulette.transfer(office_id.clone(), user3, user2_id);
+
+    // the new holder pays the tax as usual
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 20),
+    );
+    paulette.pay_tax(office_id, user2.clone());
+    assert_eq!(usdc_token.balance(&Identifier::Account(user1)), 25);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(11))")] // PauletteError::NotHolder
+fn test_invalid_transfer() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    // user3 doesn't hold the office
+    paulette.transfer(office_id, user3.clone(), Identifier::Account(user3));
+}
+
+// signs `function` over `args` followed by the nonce, as the contract verifies it
+fn signed_auth(
+    e: &Env,
+    paulette: &PauletteContract,
+    signer: &Keypair,
+    function: Symbol,
+    args: impl IntoVal<Env, Vec<RawVal>>,
+    nonce: u32,
+) -> Auth {
+    let nonce = BigInt::from_u32(e, nonce);
+    let mut args: Vec<RawVal> = args.into_val(e);
+    args.push_back(nonce.clone().into_val(e));
+    Auth {
+        sig: sign(e, signer, &paulette.contract_id(), function, args),
+        nonce,
+    }
+}
+
+#[test]
+fn test_signed_transfer() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+    let (key_id, key) = generate(&e);
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    paulette.transfer(office_id.clone(), user2, key_id.clone());
+
+    // the key signs the office back over to user2
+    let auth = signed_auth(
+        &e,
+        &paulette,
+        &key,
+        symbol!("transfer"),
+        (&office_id, &user2_id),
+        0,
+    );
+    paulette.client().transfer(&office_id, &auth, &user2_id);
+
+    assert_eq!(paulette.get_owner(office_id), Some(user2_id));
+    assert_eq!(paulette.nonce_of(key_id), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_forged_transfer() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let (key_id, key) = generate(&e);
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    paulette.transfer(office_id.clone(), user2, key_id);
+
+    // user3 knows the key and its nonce, but can't sign with it
+    let public_key = BytesN::from_array(&e, &key.public.to_bytes());
+    paulette.client().transfer(
+        &office_id,
+        &ed25519_auth(&e, &public_key, 0),
+        &Identifier::Account(user3),
+    );
+}
+
+#[test]
+fn test_inheritance() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    paulette.set_heir(office_id.clone(), user2.clone(), user3_id.clone());
+
+    // user2 doesn't pay the tax, user3 claims the office within the window
+    set_timestamp(&e, 1666359075 + 604800 + 3600);
+
+    usdc_token.with_source_account(&user3).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 20),
+    );
+    paulette.claim_inheritance(office_id.clone(), user3.clone());
+
+    assert_eq!(usdc_token.balance(&user3_id), 980);
+    assert_eq!(usdc_token.balance(&Identifier::Account(user1)), 25);
+    assert_eq!(
+        paulette.get_expiry(office_id.clone()),
+        Some(TimeStamp(1666359075 + 2 * 604800 + 3600))
+    );
+
+    // user3 is now the holder
+    paulette.transfer(office_id, user3, Identifier::Account(user2));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(13))")] // PauletteError::ClaimWindowOpen
+fn test_revoke_during_claim_window() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    paulette.set_heir(office_id.clone(), user2, Identifier::Account(user3));
+
+    // the office has expired but the heir can still claim it
+    set_timestamp(&e, 1666359075 + 604800 + 3600);
+
+    paulette.revoke(
+        user1,
+        office_id,
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+}
+
+#[test]
+fn test_office_tax() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // an office created without a tax uses the contract's default
+    let default_office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    assert_eq!(paulette.get_tax(default_office_id), 20);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        Some(Tax::Flat(bigint!(&e, 50))),
+        None,
+    );
+    let auction_id = auction_of(&paulette, office_id.clone());
+    assert_eq!(paulette.get_tax(office_id.clone()), 50);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
+    assert_eq!(paulette.get_tax(office_id.clone()), 50);
+
+    // paying the tax charges the office's own amount
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 50),
+    );
+    paulette.pay_tax(office_id, user2.clone());
+    assert_eq!(usdc_token.balance(&user2_id), 940);
+}
+
+#[test]
+fn test_custom_period() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    // offices expire every day
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 86400);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    // a day later the office can be revoked
+    set_timestamp(&e, 1666359075 + 86400);
+
+    paulette.revoke(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
     );
+    assert_eq!(paulette.get_price(office_id), 50);
+
+    // offices created from now on default to a month
+    paulette.set_period(user1.clone(), TimeStamp(2592000));
+    let monthly_office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    set_timestamp(&e, 1666359075 + 86400 + 2592000);
+
+    paulette.revoke(
+        user1,
+        monthly_office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+    assert_eq!(paulette.get_price(monthly_office_id), 50);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(5))")] // PauletteError::NotExpired
+fn test_office_period_override() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 86400);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // this office expires every month instead of every day
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        None,
+        Some(TimeStamp(2592000)),
+    );
+    let auction_id = auction_of(&paulette, office_id.clone());
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(office_id.clone(), user2.clone(), Identifier::Account(user2));
+
+    set_timestamp(&e, 1666359075 + 86400);
+
+    paulette.revoke(
+        user1,
+        office_id,
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+}
+
+#[test]
+fn test_prepay() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    // user2 pays three weeks upfront
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 60),
+    );
+    paulette.pay_tax_periods(office_id, user2.clone(), 3);
+    assert_eq!(usdc_token.balance(&user2_id), 935);
+    assert_eq!(usdc_token.balance(&Identifier::Account(user1)), 65);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(17))")] // PauletteError::PrepayTooLong
+fn test_prepay_past_horizon() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // offices can't be paid for more than four weeks ahead
+    paulette.set_max_prepay(user1.clone(), TimeStamp(4 * 604800));
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 80),
+    );
+    paulette.pay_tax_periods(office_id, user2.clone(), 4);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(42))")] // PauletteError::Overflow
+fn test_expiry_overflow() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), u64::MAX / 4);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // four more periods run past the end of time
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    paulette.pay_tax_periods(office_id, user2.clone(), 4);
+}
+
+#[test]
+fn test_late_payment() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // one day of grace with a 10% surcharge
+    paulette.set_grace(user1.clone(), TimeStamp(86400), 1000);
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    set_timestamp(&e, 1666359075 + 604800 + 3600);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 22),
+    );
+    paulette.pay_tax(office_id, user2.clone());
+    assert_eq!(usdc_token.balance(&user2_id), 973);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(18))")] // PauletteError::GracePeriodOver
+fn test_payment_after_grace() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    paulette.set_grace(user1.clone(), TimeStamp(86400), 1000);
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    set_timestamp(&e, 1666359075 + 604800 + 86400);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 22),
+    );
+    paulette.pay_tax(office_id, user2.clone());
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(19))")] // PauletteError::InGracePeriod
+fn test_revoke_during_grace() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    paulette.set_grace(user1.clone(), TimeStamp(86400), 1000);
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    set_timestamp(&e, 1666359075 + 604800 + 3600);
+
+    paulette.revoke(
+        user1,
+        office_id,
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+}
+
+#[test]
+fn test_self_assessed_tax() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // the tax is 10% of the declared valuation
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 10),
+            slope: bigint!(&e, 900),
+        }),
+        Some(Tax::SelfAssessed(1000)),
+        None,
+    );
+    let auction_id = auction_of(&paulette, office_id.clone());
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
+
+    // valued at the price it was bought for until the holder declares otherwise
+    assert_eq!(paulette.get_tax(office_id.clone()), 5);
+    paulette.set_valuation(office_id.clone(), user2.clone(), bigint!(&e, 100));
+    assert_eq!(paulette.get_tax(office_id.clone()), 10);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 10),
+    );
+    paulette.pay_tax(office_id.clone(), user2.clone());
+    assert_eq!(usdc_token.balance(&user2_id), 940);
+
+    // user3 buys the office from user2 at the declared valuation
+    usdc_token.with_source_account(&user3).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 100),
+    );
+    paulette.force_buy(
+        office_id.clone(),
+        user3.clone(),
+        bigint!(&e, 200),
+        bigint!(&e, 100),
+    );
+
+    assert_eq!(usdc_token.balance(&user2_id), 1040);
+    assert_eq!(usdc_token.balance(&user3_id), 900);
+    assert_eq!(paulette.get_tax(office_id.clone()), 20);
+
+    // user3 is now the holder
+    paulette.transfer(office_id, user3, user2_id);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(20))")] // PauletteError::NotSelfAssessed
+fn test_force_buy_flat_tax() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    paulette.force_buy(office_id, user3, bigint!(&e, 100), bigint!(&e, 100));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(37))")] // PauletteError::PriceAboveLimit
+fn test_force_buy_above_limit() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        Some(Tax::SelfAssessed(1000)),
+        None,
+    );
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.buy(
+        office_id.clone(),
+        user2.clone(),
+        Identifier::Account(user2.clone()),
+    );
+
+    // user2 raises the valuation before user3's purchase goes through
+    approve(&e, &usdc_token, &user3, &paulette_id, 1000);
+    paulette.set_valuation(office_id.clone(), user2, bigint!(&e, 1000));
+    paulette.force_buy(office_id, user3, bigint!(&e, 200), bigint!(&e, 100));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(41))")] // PauletteError::InvalidValuation
+fn test_negative_valuation() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        Some(Tax::SelfAssessed(1000)),
+        None,
+    );
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.buy(
+        office_id.clone(),
+        user2.clone(),
+        Identifier::Account(user2.clone()),
+    );
+
+    paulette.set_valuation(office_id, user2, BigInt::from_i64(&e, -1));
+}
+
+#[test]
+fn test_raise_prepaid_valuation() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        Some(Tax::SelfAssessed(1000)),
+        None,
+    );
+    approve(&e, &usdc_token, &user2, &paulette_id, 120);
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
+
+    // two more weeks are paid at a valuation of 100
+    paulette.pay_tax_periods(office_id.clone(), user2.clone(), 2);
+    assert_eq!(
+        paulette.get_expiry(office_id.clone()),
+        Some(TimeStamp(1666359075 + 3 * 604800))
+    );
+
+    // at twice the valuation the tax paid covers half as long
+    paulette.set_valuation(office_id.clone(), user2, bigint!(&e, 200));
+    assert_eq!(
+        paulette.get_expiry(office_id),
+        Some(TimeStamp(1666359075 + 3 * 604800 / 2))
+    );
+}
+
+#[test]
+fn test_proportional_tax() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // the Paulette's historical one-sixtieth of the office's price
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 600),
+            min_price: bigint!(&e, 60),
+            slope: bigint!(&e, 10),
+        }),
+        Some(Tax::Proportional(166)),
+        None,
+    );
+    let auction_id = auction_of(&paulette, office_id.clone());
+
+    // 30 seconds into the auction the price is 597
+    set_timestamp(&e, 1666359075 + 30);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
+    assert_eq!(usdc_token.balance(&user2_id), 403);
+    assert_eq!(paulette.get_tax(office_id.clone()), 9);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 9),
+    );
+    paulette.pay_tax(office_id, user2.clone());
+    assert_eq!(usdc_token.balance(&user2_id), 394);
+}
+
+#[test]
+fn test_fixed_price() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user1_id = Identifier::Account(user1.clone());
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        None,
+        None,
+    );
+    assert_eq!(
+        paulette.get_office(office_id.clone()),
+        OfficeStatus::ForSale(Listing {
+            mechanism: SaleMechanism::FixedPrice(bigint!(&e, 100)),
+            price: bigint!(&e, 100),
+        })
+    );
+
+    // the price doesn't move and is paid to the treasury through the contract
+    set_timestamp(&e, 1666359075 + 3600);
+    assert_eq!(paulette.get_price(office_id.clone()), 100);
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 100),
+    );
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
+
+    assert_eq!(usdc_token.balance(&user1_id), 100);
+    assert_eq!(paulette.get_owner(office_id.clone()), Some(user2_id));
+    assert_eq!(
+        paulette.get_expiry(office_id.clone()),
+        Some(TimeStamp(1666359075 + 3600 + 604800))
+    );
+
+    // an expired office can go back on sale at a fixed price too
+    set_timestamp(&e, 1666359075 + 3600 + 604800);
+    paulette.revoke(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 80)),
+        None,
+        None,
+    );
+    assert_eq!(paulette.get_price(office_id), 80);
+}
+
+// creates an office sold through an hour long english auction with a reserve of 100
+fn new_english_office(e: &Env, paulette: &PauletteContract, admin: &AccountId) -> BytesN<16> {
+    let office_id = BytesN::from_array(e, &generate_office_id());
+    paulette.new_office(
+        admin.clone(),
+        office_id.clone(),
+        SaleParams::English(EnglishAuction {
+            reserve: bigint!(e, 100),
+            increment: bigint!(e, 10),
+            duration: TimeStamp(3600),
+        }),
+        None,
+        None,
+    );
+    office_id
+}
+
+fn approve(e: &Env, token: &token::Client, from: &AccountId, spender: &Identifier, amount: u32) {
+    token.with_source_account(from).approve(
+        &Signature::Invoker,
+        &BigInt::zero(e),
+        spender,
+        &BigInt::from_u32(e, amount),
+    );
+}
+
+#[test]
+fn test_english_auction() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user1_id = Identifier::Account(user1.clone());
+    let user2_id = Identifier::Account(user2.clone());
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = new_english_office(&e, &paulette, &user1);
+    assert_eq!(paulette.get_price(office_id.clone()), 100);
+
+    // the bid is held by the contract
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.place_bid(office_id.clone(), user2.clone(), bigint!(&e, 100));
+    assert_eq!(usdc_token.balance(&user2_id), 900);
+    assert_eq!(usdc_token.balance(&paulette_id), 100);
+
+    // and given back when outbid
+    set_timestamp(&e, 1666359075 + 1800);
+    approve(&e, &usdc_token, &user3, &paulette_id, 110);
+    paulette.place_bid(office_id.clone(), user3.clone(), bigint!(&e, 110));
+    assert_eq!(usdc_token.balance(&user2_id), 1000);
+    assert_eq!(usdc_token.balance(&user3_id), 890);
+    assert_eq!(paulette.get_price(office_id.clone()), 110);
+
+    set_timestamp(&e, 1666359075 + 3600);
+    paulette.settle(office_id.clone());
+    assert_eq!(usdc_token.balance(&paulette_id), 0);
+    assert_eq!(usdc_token.balance(&user1_id), 110);
+    assert_eq!(
+        paulette.get_office(office_id),
+        OfficeStatus::Held(Tenure {
+            user: user3_id,
+            expires: TimeStamp(1666359075 + 3600 + 604800),
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(31))")] // PauletteError::BidTooLow
+fn test_bid_below_increment() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = new_english_office(&e, &paulette, &user1);
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.place_bid(office_id.clone(), user2, bigint!(&e, 100));
+
+    approve(&e, &usdc_token, &user3, &paulette_id, 105);
+    paulette.place_bid(office_id, user3, bigint!(&e, 105));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(30))")] // PauletteError::AuctionOpen
+fn test_settle_before_deadline() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = new_english_office(&e, &paulette, &user1);
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.place_bid(office_id.clone(), user2, bigint!(&e, 100));
+
+    set_timestamp(&e, 1666359075 + 3599);
+    paulette.settle(office_id);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(40))")] // PauletteError::InvalidIncrement
+fn test_zero_increment() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    set_timestamp(&e, 1666359075);
+
+    paulette.new_office(
+        user1,
+        BytesN::from_array(&e, &generate_office_id()),
+        SaleParams::English(EnglishAuction {
+            reserve: bigint!(&e, 100),
+            increment: bigint!(&e, 0),
+            duration: TimeStamp(3600),
+        }),
+        None,
+        None,
+    );
+}
+
+// creates an office sold through a sealed-bid auction with a reserve of 50, an hour to commit and an hour to reveal
+fn new_sealed_office(e: &Env, paulette: &PauletteContract, admin: &AccountId) -> BytesN<16> {
+    let office_id = BytesN::from_array(e, &generate_office_id());
+    paulette.new_office(
+        admin.clone(),
+        office_id.clone(),
+        SaleParams::Sealed(SealedAuction {
+            reserve: bigint!(e, 50),
+            commit: TimeStamp(3600),
+            reveal: TimeStamp(3600),
+        }),
+        None,
+        None,
+    );
+    office_id
+}
+
+// commits `amount` backed by `deposit`, returning the salt to reveal it with
+fn commit(
+    e: &Env,
+    paulette: &PauletteContract,
+    token: &token::Client,
+    office_id: &BytesN<16>,
+    bidder: &AccountId,
+    amount: u32,
+    deposit: u32,
+) -> BytesN<32> {
+    let paulette_id = Identifier::Contract(paulette.contract_id());
+    let salt = BytesN::from_array(e, &generate_contract_id());
+    approve(e, token, bidder, &paulette_id, deposit);
+    paulette.commit_bid(
+        office_id.clone(),
+        bidder.clone(),
+        bid_hash(e, &BigInt::from_u32(e, amount), &salt),
+        BigInt::from_u32(e, deposit),
+    );
+    salt
+}
+
+#[test]
+fn test_sealed_auction() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user4 = e.accounts().generate();
+    let user1_id = Identifier::Account(user1.clone());
+    let user2_id = Identifier::Account(user2.clone());
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    mint(&e, &usdc_token, &admin1, &user4, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = new_sealed_office(&e, &paulette, &user1);
+    let salt_2 = commit(&e, &paulette, &usdc_token, &office_id, &user2, 200, 300);
+    let salt_3 = commit(&e, &paulette, &usdc_token, &office_id, &user3, 150, 150);
+    commit(&e, &paulette, &usdc_token, &office_id, &user4, 180, 200);
+    assert_eq!(usdc_token.balance(&paulette_id), 650);
+
+    // user4 never reveals
+    set_timestamp(&e, 1666359075 + 3600);
+    paulette.reveal_bid(
+        office_id.clone(),
+        user2_id.clone(),
+        bigint!(&e, 200),
+        salt_2,
+    );
+    paulette.reveal_bid(
+        office_id.clone(),
+        user3_id.clone(),
+        bigint!(&e, 150),
+        salt_3,
+    );
+
+    // user2 wins at user3's price, user4's deposit is forfeited
+    set_timestamp(&e, 1666359075 + 7200);
+    paulette.settle(office_id.clone());
+    assert_eq!(usdc_token.balance(&paulette_id), 0);
+    assert_eq!(usdc_token.balance(&user2_id), 850);
+    assert_eq!(usdc_token.balance(&user3_id), 1000);
+    assert_eq!(usdc_token.balance(&Identifier::Account(user4)), 800);
+    assert_eq!(usdc_token.balance(&user1_id), 350);
+    assert_eq!(
+        paulette.get_office(office_id),
+        OfficeStatus::Held(Tenure {
+            user: user2_id,
+            expires: TimeStamp(1666359075 + 7200 + 604800),
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(35))")] // PauletteError::HashMismatch
+fn test_reveal_wrong_bid() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = new_sealed_office(&e, &paulette, &user1);
+    let salt = commit(&e, &paulette, &usdc_token, &office_id, &user2, 200, 300);
+
+    set_timestamp(&e, 1666359075 + 3600);
+    paulette.reveal_bid(
+        office_id,
+        Identifier::Account(user2),
+        bigint!(&e, 100),
+        salt,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(33))")] // PauletteError::NotRevealPhase
+fn test_reveal_during_commit() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = new_sealed_office(&e, &paulette, &user1);
+    let salt = commit(&e, &paulette, &usdc_token, &office_id, &user2, 200, 300);
+
+    paulette.reveal_bid(
+        office_id,
+        Identifier::Account(user2),
+        bigint!(&e, 200),
+        salt,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(39))")] // PauletteError::TooManyBids
+fn test_too_many_commitments() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = new_sealed_office(&e, &paulette, &user1);
+    for _ in 0..crate::MAX_COMMITMENTS {
+        let bidder = e.accounts().generate();
+        mint(&e, &usdc_token, &admin1, &bidder, 50);
+        commit(&e, &paulette, &usdc_token, &office_id, &bidder, 50, 50);
+    }
+
+    // the auction is full, settling it has to stay within budget
+    let bidder = e.accounts().generate();
+    mint(&e, &usdc_token, &admin1, &bidder, 50);
+    commit(&e, &paulette, &usdc_token, &office_id, &bidder, 50, 50);
+}
+
+#[test]
+fn test_buy_with_limit() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        None,
+        None,
+    );
+    let auction_id = auction_of(&paulette, office_id.clone());
+
+    // the price has dropped to 3, within the limit
+    set_timestamp(&e, 1666359075 + 1800);
+    approve(
+        &e,
+        &usdc_token,
+        &user2,
+        &Identifier::Contract(auction_id),
+        4,
+    );
+    paulette.buy_with_limit(
+        office_id.clone(),
+        user2.clone(),
+        user2_id.clone(),
+        bigint!(&e, 4),
+    );
+
+    assert_eq!(usdc_token.balance(&user2_id), 997);
+    assert_eq!(paulette.get_owner(office_id), Some(user2_id));
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(37))")] // PauletteError::PriceAboveLimit
+fn test_buy_above_limit() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        None,
+        None,
+    );
+
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.buy_with_limit(
+        office_id,
+        user2.clone(),
+        Identifier::Account(user2),
+        bigint!(&e, 99),
+    );
+}
+
+#[test]
+fn test_gift_office() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        None,
+        None,
+    );
+
+    // user2 pays for the office, user3 receives it
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.buy(office_id.clone(), user2, user3_id.clone());
+
+    assert_eq!(usdc_token.balance(&user2_id), 900);
+    assert_eq!(usdc_token.balance(&user3_id), 0);
+    assert_eq!(
+        paulette.get_owner(office_id.clone()),
+        Some(user3_id.clone())
+    );
+    assert_eq!(paulette.offices_of(user3_id), vec![&e, office_id]);
+    assert_eq!(paulette.office_count(user2_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(7))")] // PauletteError::IncorrectNonce
+fn test_buy_incorrect_nonce() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let key = BytesN::from_array(&e, &[7; 32]);
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        None,
+        None,
+    );
+
+    // the buyer's next nonce is 0
+    paulette.client().buy(
+        &office_id,
+        &ed25519_auth(&e, &key, 1),
+        &Identifier::Account(user2),
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_forged_buy() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let (_key_id, key) = generate(&e);
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        None,
+        None,
+    );
+
+    // user2 tries to spend the key's allowance without its signature
+    let public_key = BytesN::from_array(&e, &key.public.to_bytes());
+    paulette.client().buy(
+        &office_id,
+        &ed25519_auth(&e, &public_key, 0),
+        &Identifier::Account(user2),
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_forged_tax_payment() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let (_key_id, key) = generate(&e);
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    // user2 tries to pay their own tax out of the key's allowance without its signature
+    let public_key = BytesN::from_array(&e, &key.public.to_bytes());
+    e.set_source_account(&user2);
+    paulette
+        .client()
+        .pay_tax_periods(&office_id, &ed25519_auth(&e, &public_key, 0), &4);
+}
+
+#[test]
+fn test_office_status() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    assert_eq!(
+        paulette.get_office(office_id.clone()),
+        OfficeStatus::Unknown
+    );
+    assert_eq!(paulette.get_owner(office_id.clone()), None);
+    assert_eq!(paulette.get_expiry(office_id.clone()), None);
+    assert!(!paulette.is_expired(office_id.clone()));
+
+    paulette.new_office(
+        user1,
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        None,
+        None,
+    );
+    let auction_id = auction_of(&paulette, office_id.clone());
+    assert_eq!(
+        paulette.get_office(office_id.clone()),
+        OfficeStatus::ForSale(Listing {
+            mechanism: SaleMechanism::Dutch(auction_id.clone()),
+            price: bigint!(&e, 5),
+        })
+    );
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
+
+    let tenure = Tenure {
+        user: user2_id.clone(),
+        expires: TimeStamp(1666359075 + 604800),
+    };
+    assert_eq!(
+        paulette.get_office(office_id.clone()),
+        OfficeStatus::Held(tenure.clone())
+    );
+    assert_eq!(paulette.get_owner(office_id.clone()), Some(user2_id));
+    assert_eq!(
+        paulette.get_expiry(office_id.clone()),
+        Some(TimeStamp(1666359075 + 604800))
+    );
+    assert!(!paulette.is_expired(office_id.clone()));
+
+    set_timestamp(&e, 1666359075 + 604800);
+    assert_eq!(
+        paulette.get_office(office_id.clone()),
+        OfficeStatus::Expired(tenure)
+    );
+    assert!(paulette.is_expired(office_id));
+}
+
+#[test]
+fn test_registry() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_1 = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    let office_2 = BytesN::from_array(&e, &generate_office_id());
+    let office_3 = BytesN::from_array(&e, &generate_office_id());
+    for office_id in [office_2.clone(), office_3.clone()] {
+        paulette.new_office(
+            user1.clone(),
+            office_id,
+            SaleParams::Dutch(DutchAuction {
+                price: bigint!(&e, 5),
+                min_price: bigint!(&e, 1),
+                slope: bigint!(&e, 900),
+            }),
+            None,
+            None,
+        );
+    }
+
+    assert_eq!(
+        paulette.offices(0, 10),
+        vec![&e, office_1.clone(), office_2.clone(), office_3.clone()]
+    );
+    assert_eq!(paulette.offices(1, 1), vec![&e, office_2.clone()]);
+    assert_eq!(paulette.offices(3, 10), vec![&e]);
+    assert_eq!(
+        paulette.offices_for_sale(0, 10),
+        vec![&e, office_2.clone(), office_3.clone()]
+    );
+    assert_eq!(paulette.offices_held(0, 10), vec![&e, office_1.clone()]);
+
+    // revoking puts the office back up for sale
+    set_timestamp(&e, 1666359075 + 604800);
+    paulette.revoke(
+        user1,
+        office_1.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+
+    assert_eq!(
+        paulette.offices_for_sale(0, 10),
+        vec![&e, office_2, office_3, office_1]
+    );
+    assert_eq!(paulette.offices_held(0, 10), vec![&e]);
+}
+
+#[test]
+fn test_holder_index() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_1 = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    let office_2 = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    assert_eq!(
+        paulette.offices_of(user2_id.clone()),
+        vec![&e, office_1.clone(), office_2.clone()]
+    );
+    assert_eq!(paulette.office_count(user2_id.clone()), 2);
+    assert_eq!(paulette.office_count(user3_id.clone()), 0);
+
+    paulette.transfer(office_2.clone(), user2, user3_id.clone());
+    assert_eq!(
+        paulette.offices_of(user2_id.clone()),
+        vec![&e, office_1.clone()]
+    );
+    assert_eq!(paulette.offices_of(user3_id.clone()), vec![&e, office_2]);
+
+    // office_1 is revoked and bought again by user3
+    set_timestamp(&e, 1666359075 + 604800);
+
+    paulette.revoke(
+        user1,
+        office_1.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+    let auction_id = auction_of(&paulette, office_1.clone());
+    assert_eq!(paulette.office_count(user2_id.clone()), 0);
+    assert_eq!(paulette.office_count(user3_id.clone()), 1);
+
+    usdc_token.with_source_account(&user3).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_1.clone()),
+    );
+    paulette.buy(office_1.clone(), user3.clone(), user3_id.clone());
+    assert_eq!(paulette.office_count(user2_id), 0);
+    assert_eq!(paulette.office_count(user3_id.clone()), 2);
+    assert_eq!(paulette.get_owner(office_1), Some(user3_id));
+}
+
+#[test]
+fn test_events() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user1_id = Identifier::Account(user1.clone());
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_contract = BytesN::from_array(&e, &contract_paulette);
+    let paulette_id = Identifier::Contract(paulette_contract.clone());
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 5),
+            min_price: bigint!(&e, 1),
+            slope: bigint!(&e, 900),
+        }),
+        None,
+        None,
+    );
+    let auction_id = auction_of(&paulette, office_id.clone());
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id.clone()),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(office_id.clone(), user2.clone(), user2_id.clone());
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 20),
+    );
+    paulette.pay_tax(office_id.clone(), user2.clone());
+
+    set_timestamp(&e, 1666359075 + 2 * 604800);
+
+    paulette.revoke(
+        user1,
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+    let auction_1_id = auction_of(&paulette, office_id.clone());
+
+    assert_eq!(
+        contract_events(&e, &paulette_contract),
+        vec![
+            &e,
+            event(
+                &e,
+                &paulette_contract,
+                (symbol!("initialize"),),
+                (
+                    user1_id,
+                    BytesN::from_array(&e, &contract1),
+                    bigint!(&e, 20),
+                    TimeStamp(604800),
+                ),
+            ),
+            event(
+                &e,
+                &paulette_contract,
+                (symbol!("new_office"), office_id.clone()),
+                (SaleMechanism::Dutch(auction_id.clone()), bigint!(&e, 5)),
+            ),
+            event(
+                &e,
+                &paulette_contract,
+                (symbol!("buy"), office_id.clone()),
+                (
+                    user2_id.clone(),
+                    user2_id.clone(),
+                    bigint!(&e, 5),
+                    TimeStamp(1666359075 + 604800),
+                    SaleMechanism::Dutch(auction_id),
+                ),
+            ),
+            event(
+                &e,
+                &paulette_contract,
+                (symbol!("pay_tax"), office_id.clone()),
+                (
+                    user2_id.clone(),
+                    bigint!(&e, 20),
+                    TimeStamp(1666359075 + 2 * 604800),
+                ),
+            ),
+            event(
+                &e,
+                &paulette_contract,
+                (symbol!("revoke"), office_id),
+                (
+                    user2_id,
+                    SaleMechanism::Dutch(auction_1_id),
+                    bigint!(&e, 50),
+                ),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_admin_rotation() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // user1 keeps administering the contract until user3 accepts
+    paulette.propose_admin(
+        user1.clone(),
+        AdminSet {
+            members: vec![&e, Identifier::Account(user3.clone())],
+            threshold: 1,
+        },
+    );
+    buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    paulette.accept_admin(user3.clone());
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user3, &user2);
+
+    // auction proceeds now go to user3
+    assert_eq!(usdc_token.balance(&Identifier::Account(user3)), 5);
+    assert_eq!(
+        paulette.get_owner(office_id),
+        Some(Identifier::Account(user2))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(6))")] // PauletteError::NotAuthorized
+fn test_rotated_admin() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    paulette.propose_admin(
+        user1.clone(),
+        AdminSet {
+            members: vec![&e, Identifier::Account(user3.clone())],
+            threshold: 1,
+        },
+    );
+    paulette.accept_admin(user3);
+
+    // user1 isn't the admin anymore
+    buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(21))")] // PauletteError::NotPendingAdmin
+fn test_accept_admin_not_proposed() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    paulette.propose_admin(
+        user1,
+        AdminSet {
+            members: vec![&e, Identifier::Account(user3)],
+            threshold: 1,
+        },
+    );
+    paulette.accept_admin(user2);
+}
+
+fn invoker_auth(e: &Env) -> Auth {
+    Auth {
+        sig: Signature::Invoker,
+        nonce: BigInt::zero(e),
+    }
+}
+
+fn ed25519_auth(e: &Env, public_key: &BytesN<32>, nonce: u32) -> Auth {
+    Auth {
+        sig: Signature::Ed25519(Ed25519Signature {
+            public_key: public_key.clone(),
+            signature: BytesN::from_array(e, &[0; 64]),
+        }),
+        nonce: BigInt::from_u32(e, nonce),
+    }
+}
+
+// hands the contract over to a 2-of-2 set of `member` and a fresh ed25519 key, paying `treasury`
+fn rotate_to_multisig(
+    e: &Env,
+    paulette: &PauletteContract,
+    admin: &AccountId,
+    member: &AccountId,
+    treasury: &Identifier,
+) -> Keypair {
+    let (key_id, key) = generate(e);
+    let new_admin = AdminSet {
+        members: vec![e, Identifier::Account(member.clone()), key_id],
+        threshold: 2,
+    };
+    paulette.set_treasury(admin.clone(), treasury.clone());
+    paulette.propose_admin(admin.clone(), new_admin.clone());
+
+    e.set_source_account(member);
+    paulette.client().accept_admin(&vec![
+        e,
+        invoker_auth(e),
+        signed_auth(e, paulette, &key, symbol!("acc_admin"), (new_admin,), 0),
+    ]);
+    key
+}
+
+#[test]
+#[should_panic]
+fn test_accept_changed_proposal() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let (key_id, key) = generate(&e);
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    let proposed = AdminSet {
+        members: vec![&e, key_id.clone()],
+        threshold: 1,
+    };
+    paulette.propose_admin(user1.clone(), proposed.clone());
+    let acceptance = signed_auth(&e, &paulette, &key, symbol!("acc_admin"), (proposed,), 0);
+
+    // the proposal changes before the key's acceptance is submitted
+    paulette.propose_admin(
+        user1,
+        AdminSet {
+            members: vec![&e, key_id, Identifier::Account(user2)],
+            threshold: 1,
+        },
+    );
+    paulette.client().accept_admin(&vec![&e, acceptance]);
+}
+
+#[test]
+fn test_multisig_admin() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user4 = e.accounts().generate();
+    let user3_id = Identifier::Account(user3.clone());
+    let user4_id = Identifier::Account(user4);
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let key = rotate_to_multisig(&e, &paulette, &user1, &user3, &user4_id);
+    assert_eq!(paulette.admins().threshold, 2);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    let params = SaleParams::Dutch(DutchAuction {
+        price: bigint!(&e, 5),
+        min_price: bigint!(&e, 1),
+        slope: bigint!(&e, 900),
+    });
+    let auth = signed_auth(
+        &e,
+        &paulette,
+        &key,
+        symbol!("new_office"),
+        (&office_id, &params, &None::<Tax>, &None::<TimeStamp>),
+        1,
+    );
+    e.set_source_account(&user3);
+    paulette.client().new_office(
+        &vec![&e, invoker_auth(&e), auth],
+        &office_id,
+        &params,
+        &None,
+        &None,
+    );
+    let public_key = BytesN::from_array(&e, &key.public.to_bytes());
+    assert_eq!(paulette.nonce_of(Identifier::Ed25519(public_key)), 2);
+    let auction_id = auction_of(&paulette, office_id.clone());
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &Identifier::Contract(auction_id),
+        &paulette.get_price(office_id.clone()),
+    );
+    paulette.buy(office_id, user2.clone(), Identifier::Account(user2));
+
+    // the proceeds go to the treasury rather than to one of the admins
+    assert_eq!(usdc_token.balance(&user4_id), 5);
+    assert_eq!(usdc_token.balance(&user3_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(38))")] // PauletteError::TreasuryNotSet
+fn test_multisig_without_treasury() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user4 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    paulette.propose_admin(
+        user1,
+        AdminSet {
+            members: vec![
+                &e,
+                Identifier::Account(user3.clone()),
+                Identifier::Account(user4),
+            ],
+            threshold: 1,
+        },
+    );
+    paulette.accept_admin(user3);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(23))")] // PauletteError::ThresholdNotMet
+fn test_multisig_threshold_not_met() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    rotate_to_multisig(
+        &e,
+        &paulette,
+        &user1,
+        &user3,
+        &Identifier::Account(user1.clone()),
+    );
+
+    // only one of the two admins signs
+    buy_new_office(&e, &paulette, &usdc_token, &user3, &user2);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(22))")] // PauletteError::DuplicateSigner
+fn test_multisig_duplicate_signer() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    let key = rotate_to_multisig(
+        &e,
+        &paulette,
+        &user1,
+        &user3,
+        &Identifier::Account(user1.clone()),
+    );
+
+    // the same key can't make up the threshold on its own
+    let period = TimeStamp(86400);
+    paulette.client().set_period(
+        &vec![
+            &e,
+            signed_auth(&e, &paulette, &key, symbol!("set_period"), (&period,), 1),
+            signed_auth(&e, &paulette, &key, symbol!("set_period"), (&period,), 2),
+        ],
+        &period,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_multisig_forged_signature() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    let key = rotate_to_multisig(
+        &e,
+        &paulette,
+        &user1,
+        &user3,
+        &Identifier::Account(user1.clone()),
+    );
+
+    // user3 knows the other member's key and nonce, but can't sign with it
+    let public_key = BytesN::from_array(&e, &key.public.to_bytes());
+    e.set_source_account(&user3);
+    paulette.client().set_period(
+        &vec![&e, invoker_auth(&e), ed25519_auth(&e, &public_key, 1)],
+        &TimeStamp(86400),
+    );
+}
+
+#[test]
+fn test_roles() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user4 = e.accounts().generate();
+    let user3_id = Identifier::Account(user3.clone());
+    let user4_id = Identifier::Account(user4.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // user3 creates and revokes offices, user4 sets the tax parameters
+    paulette.grant_role(user1.clone(), user3_id.clone(), Role::Registrar);
+    paulette.grant_role(user1.clone(), user3_id.clone(), Role::Collector);
+    paulette.grant_role(user1.clone(), user4_id.clone(), Role::Treasurer);
+    assert!(paulette.has_role(user3_id.clone(), Role::Registrar));
+    assert!(!paulette.has_role(user3_id.clone(), Role::Treasurer));
+
+    paulette.set_tax(user4.clone(), bigint!(&e, 30));
+    paulette.set_period(user4.clone(), TimeStamp(86400));
+    paulette.set_grace(user4, TimeStamp(3600), 1000);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user3, &user2);
+    assert_eq!(paulette.get_tax(office_id.clone()), 30);
+    assert_eq!(
+        paulette.get_expiry(office_id.clone()),
+        Some(TimeStamp(1666359075 + 86400))
+    );
+
+    set_timestamp(&e, 1666359075 + 86400 + 3600);
+    paulette.revoke(
+        user3,
+        office_id.clone(),
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+    assert_eq!(paulette.get_owner(office_id), None);
+
+    // the admin keeps its rights
+    buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(6))")] // PauletteError::NotAuthorized
+fn test_revoked_role() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    paulette.grant_role(user1.clone(), user3_id.clone(), Role::Registrar);
+    buy_new_office(&e, &paulette, &usdc_token, &user3, &user2);
+
+    paulette.revoke_role(user1, user3_id, Role::Registrar);
+    buy_new_office(&e, &paulette, &usdc_token, &user3, &user2);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(6))")] // PauletteError::NotAuthorized
+fn test_role_scope() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    paulette.grant_role(
+        user1.clone(),
+        Identifier::Account(user3.clone()),
+        Role::Registrar,
+    );
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user3, &user2);
+
+    // registrars can't revoke offices
+    set_timestamp(&e, 1666359075 + 604800);
+    paulette.revoke(
+        user3,
+        office_id,
+        SaleParams::Dutch(DutchAuction {
+            price: bigint!(&e, 50),
+            min_price: bigint!(&e, 5),
+            slope: bigint!(&e, 1800),
+        }),
+        None,
+        None,
+    );
+}
+
+#[test]
+fn test_pause() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    set_timestamp(&e, 1666359075 + 100);
+    paulette.pause(user1.clone(), false);
+    assert!(paulette.is_paused());
+
+    // taxes can still be paid
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 20),
+    );
+    paulette.pay_tax(office_id.clone(), user2.clone());
+
+    // the holder could pay, so the office isn't extended
+    set_timestamp(&e, 1666359075 + 1100);
+    paulette.unpause(user1.clone());
+    assert!(!paulette.is_paused());
+    assert_eq!(
+        paulette.get_expiry(office_id),
+        Some(TimeStamp(1666359075 + 2 * 604800))
+    );
+
+    buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+}
+
+#[test]
+fn test_pause_blocking_tax() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+
+    // the office doesn't expire while its tax can't be paid
+    set_timestamp(&e, 1666359075 + 100);
+    paulette.pause(user1.clone(), true);
+    set_timestamp(&e, 1666359075 + 604800 + 100);
+    assert!(!paulette.is_expired(office_id.clone()));
+
+    // and lasts as long as the contract was paused
+    paulette.unpause(user1.clone());
+    assert_eq!(
+        paulette.get_expiry(office_id.clone()),
+        Some(TimeStamp(1666359075 + 2 * 604800))
+    );
+
+    // offices bought afterwards aren't extended
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    assert_eq!(
+        paulette.get_expiry(office_id),
+        Some(TimeStamp(1666359075 + 2 * 604800 + 100))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(25))")] // PauletteError::Paused
+fn test_buy_while_paused() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    paulette.pause(user1.clone(), false);
+    buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(25))")] // PauletteError::Paused
+fn test_pay_tax_while_paused() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    paulette.pause(user1, true);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 20),
+    );
+    paulette.pay_tax(office_id, user2.clone());
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(25))")] // PauletteError::Paused
+fn test_force_buy_while_paused() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    mint(&e, &usdc_token, &admin1, &user3, 1000);
+    set_timestamp(&e, 1666359075);
+
+    let office_id = BytesN::from_array(&e, &generate_office_id());
+    paulette.new_office(
+        user1.clone(),
+        office_id.clone(),
+        SaleParams::FixedPrice(bigint!(&e, 100)),
+        Some(Tax::SelfAssessed(1000)),
+        None,
+    );
+    approve(&e, &usdc_token, &user2, &paulette_id, 100);
+    paulette.buy(office_id.clone(), user2.clone(), Identifier::Account(user2));
+
+    paulette.pause(user1, false);
+    approve(&e, &usdc_token, &user3, &paulette_id, 100);
+    paulette.force_buy(office_id, user3, bigint!(&e, 200), bigint!(&e, 100));
+}
+
+// a contract holding what the first release stored: a plain admin, the tax, one held and one listed office
+fn create_paulette_contract_v0(
+    e: &Env,
+    admin: &AccountId,
+    token_id: &[u8; 32],
+    held: (&BytesN<16>, &AccountId),
+    for_sale: (&BytesN<16>, &BytesN<32>),
+) -> ([u8; 32], PauletteContract) {
+    let id = generate_contract_id();
+    register_paulette(e, &id);
+    e.as_contract(&BytesN::from_array(e, &id), || {
+        e.data()
+            .set(DataKey::Admin, Identifier::Account(admin.clone()));
+        e.data()
+            .set(DataKey::TokenId, BytesN::from_array(e, token_id));
+        e.data().set(DataKey::Tax, bigint!(e, 20));
+        e.data().set(
+            DataKey::Bought(held.0.clone()),
+            OfficeV0 {
+                user: Identifier::Account(held.1.clone()),
+                expires: TimeStamp(1666359075 + 604800),
+            },
+        );
+        e.data()
+            .set(DataKey::ForSale(for_sale.0.clone()), for_sale.1.clone());
+    });
+    (id, PauletteContract::new(e, &id))
+}
+
+#[test]
+fn test_migrate() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user2_id = Identifier::Account(user2.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let held_id = BytesN::from_array(&e, &generate_office_id());
+    let for_sale_id = BytesN::from_array(&e, &generate_office_id());
+    let auction_id = register_auction(&e);
+    let (contract_paulette, paulette) = create_paulette_contract_v0(
+        &e,
+        &user1,
+        &contract1,
+        (&held_id, &user2),
+        (&for_sale_id, &auction_id),
+    );
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+    assert_eq!(paulette.version(), 0);
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    paulette.migrate(
+        user1.clone(),
+        e.install_contract_wasm(auction::WASM),
+        vec![&e, held_id.clone(), for_sale_id.clone()],
+        true,
+    );
+    assert_eq!(paulette.version(), 1);
+    assert_eq!(
+        paulette.offices(0, 10),
+        vec![&e, held_id.clone(), for_sale_id.clone()]
+    );
+    assert_eq!(paulette.offices_for_sale(0, 10), vec![&e, for_sale_id]);
+    assert_eq!(
+        paulette.offices_of(user2_id.clone()),
+        vec![&e, held_id.clone()]
+    );
+
+    // the migrated office keeps its weekly flat tax
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 20),
+    );
+    paulette.pay_tax(held_id.clone(), user2.clone());
+    assert_eq!(
+        paulette.get_expiry(held_id),
+        Some(TimeStamp(1666359075 + 2 * 604800))
+    );
+    assert_eq!(usdc_token.balance(&Identifier::Account(user1.clone())), 20);
+
+    // and its admin still administers the contract
+    buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(27))")] // PauletteError::AlreadyMigrated
+fn test_migrate_current_version() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let (_contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+
+    paulette.migrate(
+        user1,
+        e.install_contract_wasm(auction::WASM),
+        Vec::new(&e),
+        true,
+    );
+}
+
+#[test]
+fn test_migrate_in_batches() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let held_id = BytesN::from_array(&e, &generate_office_id());
+    let for_sale_id = BytesN::from_array(&e, &generate_office_id());
+    let auction_id = register_auction(&e);
+    let (_contract_paulette, paulette) = create_paulette_contract_v0(
+        &e,
+        &user1,
+        &contract1,
+        (&held_id, &user2),
+        (&for_sale_id, &auction_id),
+    );
+    set_timestamp(&e, 1666359075);
+
+    let auction_wasm = e.install_contract_wasm(auction::WASM);
+    paulette.migrate(
+        user1.clone(),
+        auction_wasm.clone(),
+        vec![&e, held_id.clone()],
+        false,
+    );
+    assert_eq!(paulette.version(), 0);
+    assert_eq!(paulette.offices(0, 10), vec![&e, held_id.clone()]);
+
+    // repeating an office that was already carried over leaves it alone
+    paulette.migrate(
+        user1,
+        auction_wasm,
+        vec![&e, held_id.clone(), for_sale_id.clone()],
+        true,
+    );
+    assert_eq!(paulette.version(), 1);
+    assert_eq!(
+        paulette.offices(0, 10),
+        vec![&e, held_id.clone(), for_sale_id]
+    );
+    assert_eq!(
+        paulette.get_expiry(held_id),
+        Some(TimeStamp(1666359075 + 604800))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Status(ContractError(43))")] // PauletteError::Migrating
+fn test_pay_tax_while_migrating() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let held_id = BytesN::from_array(&e, &generate_office_id());
+    let for_sale_id = BytesN::from_array(&e, &generate_office_id());
+    let auction_id = register_auction(&e);
+    let (_contract_paulette, paulette) = create_paulette_contract_v0(
+        &e,
+        &user1,
+        &contract1,
+        (&held_id, &user2),
+        (&for_sale_id, &auction_id),
+    );
+    set_timestamp(&e, 1666359075);
+
+    paulette.migrate(
+        user1,
+        e.install_contract_wasm(auction::WASM),
+        vec![&e, held_id.clone()],
+        false,
+    );
+    paulette.pay_tax(held_id, user2);
+}
+
+#[test]
+fn test_migrate_left_out_office() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+
+    let (contract1, _usdc_token) = create_token_contract(&e, &admin1);
+    let held_id = BytesN::from_array(&e, &generate_office_id());
+    let for_sale_id = BytesN::from_array(&e, &generate_office_id());
+    let auction_id = register_auction(&e);
+    let (_contract_paulette, paulette) = create_paulette_contract_v0(
+        &e,
+        &user1,
+        &contract1,
+        (&held_id, &user2),
+        (&for_sale_id, &auction_id),
+    );
+    set_timestamp(&e, 1666359075);
+
+    let auction_wasm = e.install_contract_wasm(auction::WASM);
+    paulette.migrate(
+        user1.clone(),
+        auction_wasm.clone(),
+        vec![&e, held_id.clone()],
+        true,
+    );
+    assert_eq!(paulette.version(), 1);
+
+    // the listed office was forgotten, it reads as unknown instead of panicking
+    assert_eq!(
+        paulette.get_office(for_sale_id.clone()),
+        OfficeStatus::Unknown
+    );
+
+    paulette.migrate(user1, auction_wasm, vec![&e, for_sale_id.clone()], false);
+    assert_eq!(paulette.offices_for_sale(0, 10), vec![&e, for_sale_id]);
+    assert_eq!(paulette.version(), 1);
+}
+
+#[test]
+fn test_treasury() {
+    let e: Env = Default::default();
+    let admin1 = e.accounts().generate(); // generating the usdc admin
+
+    let user1 = e.accounts().generate();
+    let user2 = e.accounts().generate();
+    let user3 = e.accounts().generate();
+    let user3_id = Identifier::Account(user3.clone());
+
+    let (contract1, usdc_token) = create_token_contract(&e, &admin1);
+    let (contract_paulette, paulette) =
+        create_paulette_contract(&e, &user1, &contract1, bigint!(&e, 20), 604800);
+    let paulette_id = Identifier::Contract(BytesN::from_array(&e, &contract_paulette));
+
+    mint(&e, &usdc_token, &admin1, &user2, 1000);
+    set_timestamp(&e, 1666359075);
+
+    // user3 is the treasury, both auction proceeds and taxes go there
+    paulette.set_treasury(user1.clone(), user3_id.clone());
+    let office_id = buy_new_office(&e, &paulette, &usdc_token, &user1, &user2);
+    assert_eq!(usdc_token.balance(&user3_id), 5);
+
+    usdc_token.with_source_account(&user2).approve(
+        &Signature::Invoker,
+        &BigInt::zero(&e),
+        &paulette_id,
+        &bigint!(&e, 20),
+    );
+    paulette.pay_tax(office_id, user2.clone());
+
+    assert_eq!(usdc_token.balance(&user3_id), 25);
+    assert_eq!(usdc_token.balance(&Identifier::Account(user1)), 0);
 }
@@ -1,9 +1,11 @@
 #![cfg(any(test, feature = "testutils"))]
 
-use crate::{Auth, PauletteContractClient};
+use crate::{
+    AdminSet, Auth, OfficeStatus, PauletteContractClient, Role, SaleParams, Tax, TimeStamp,
+};
 use soroban_auth::Identifier;
 
-use soroban_sdk::{AccountId, BigInt, BytesN, Env};
+use soroban_sdk::{AccountId, BigInt, BytesN, Env, Vec};
 
 pub fn register_test_contract(e: &Env, contract_id: &[u8; 32]) {
     let contract_id = BytesN::from_array(e, contract_id);
@@ -16,10 +18,24 @@ pub struct PauletteContract {
 }
 
 impl PauletteContract {
-    fn client(&self) -> PauletteContractClient {
+    pub fn client(&self) -> PauletteContractClient {
         PauletteContractClient::new(&self.env, &self.contract_id)
     }
 
+    fn invoker_auths(&self) -> Vec<Auth> {
+        soroban_sdk::vec![
+            &self.env,
+            Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            }
+        ]
+    }
+
+    pub fn contract_id(&self) -> BytesN<32> {
+        self.contract_id.clone()
+    }
+
     pub fn new(env: &Env, contract_id: &[u8; 32]) -> Self {
         Self {
             env: env.clone(),
@@ -27,15 +43,102 @@ impl PauletteContract {
         }
     }
 
-    pub fn initialize(&self, admin: &Identifier, token_id: &[u8; 32], tax: BigInt) {
-        self.client()
-            .initialize(admin, &BytesN::from_array(&self.env, token_id), &tax);
+    pub fn initialize(
+        &self,
+        admin: &Identifier,
+        token_id: &[u8; 32],
+        auction_wasm: &BytesN<32>,
+        tax: BigInt,
+        period: TimeStamp,
+    ) {
+        self.client().initialize(
+            admin,
+            &BytesN::from_array(&self.env, token_id),
+            auction_wasm,
+            &tax,
+            &period,
+        );
+    }
+
+    pub fn version(&self) -> u32 {
+        self.client().version()
+    }
+
+    pub fn migrate(
+        &self,
+        admin: AccountId,
+        auction_wasm: BytesN<32>,
+        ids: Vec<BytesN<16>>,
+        done: bool,
+    ) {
+        self.env.set_source_account(&admin);
+        self.client().migrate(
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &auction_wasm,
+            &ids,
+            &done,
+        )
     }
 
     pub fn nonce(&self) -> BigInt {
         self.client().nonce()
     }
 
+    pub fn nonce_of(&self, id: Identifier) -> BigInt {
+        self.client().nonce_of(&id)
+    }
+
+    pub fn admins(&self) -> AdminSet {
+        self.client().admins()
+    }
+
+    pub fn propose_admin(&self, admin: AccountId, new_admin: AdminSet) {
+        self.env.set_source_account(&admin);
+        self.client()
+            .propose_admin(&self.invoker_auths(), &new_admin)
+    }
+
+    pub fn accept_admin(&self, new_admin: AccountId) {
+        self.env.set_source_account(&new_admin);
+        self.client().accept_admin(&self.invoker_auths())
+    }
+
+    pub fn grant_role(&self, admin: AccountId, id: Identifier, role: Role) {
+        self.env.set_source_account(&admin);
+        self.client().grant_role(&self.invoker_auths(), &id, &role)
+    }
+
+    pub fn revoke_role(&self, admin: AccountId, id: Identifier, role: Role) {
+        self.env.set_source_account(&admin);
+        self.client().revoke_role(&self.invoker_auths(), &id, &role)
+    }
+
+    pub fn has_role(&self, id: Identifier, role: Role) -> bool {
+        self.client().has_role(&id, &role)
+    }
+
+    pub fn pause(&self, admin: AccountId, block_tax: bool) {
+        self.env.set_source_account(&admin);
+        self.client().pause(&self.invoker_auths(), &block_tax)
+    }
+
+    pub fn unpause(&self, admin: AccountId) {
+        self.env.set_source_account(&admin);
+        self.client().unpause(&self.invoker_auths())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.client().is_paused()
+    }
+
+    pub fn set_treasury(&self, admin: AccountId, treasury: Identifier) {
+        self.env.set_source_account(&admin);
+        self.client().set_treasury(&self.invoker_auths(), &treasury)
+    }
+
     pub fn get_price(&self, id: BytesN<16>) -> BigInt {
         self.client().get_price(&id)
     }
@@ -44,53 +147,246 @@ impl PauletteContract {
         &self,
         admin: AccountId,
         id: BytesN<16>,
-        auction: BytesN<32>,
-        price: BigInt,
-        min_price: BigInt,
-        slope: BigInt,
+        params: SaleParams,
+        tax: Option<Tax>,
+        period: Option<TimeStamp>,
     ) {
         self.env.set_source_account(&admin);
-        self.client().new_office(
+        self.client()
+            .new_office(&self.invoker_auths(), &id, &params, &tax, &period)
+    }
+
+    pub fn buy(&self, id: BytesN<16>, buyer: AccountId, beneficiary: Identifier) {
+        self.env.set_source_account(&buyer);
+        self.client().buy(
+            &id,
             &Auth {
                 sig: soroban_auth::Signature::Invoker,
                 nonce: BigInt::zero(&self.env),
             },
+            &beneficiary,
+        );
+    }
+
+    pub fn buy_with_limit(
+        &self,
+        id: BytesN<16>,
+        buyer: AccountId,
+        beneficiary: Identifier,
+        max_price: BigInt,
+    ) {
+        self.env.set_source_account(&buyer);
+        self.client().buy_with_limit(
             &id,
-            &auction,
-            &price,
-            &min_price,
-            &slope,
-        )
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &beneficiary,
+            &max_price,
+        );
     }
 
-    pub fn buy(&self, id: BytesN<16>, buyer: Identifier) {
-        self.client().buy(&id, &buyer);
+    pub fn get_office(&self, id: BytesN<16>) -> OfficeStatus {
+        self.client().get_office(&id)
     }
 
-    pub fn pay_tax(&self, id: BytesN<16>, payer: Identifier) {
-        self.client().pay_tax(&id, &payer)
+    pub fn get_owner(&self, id: BytesN<16>) -> Option<Identifier> {
+        self.client().get_owner(&id)
     }
 
-    pub fn revoke(
+    pub fn get_expiry(&self, id: BytesN<16>) -> Option<TimeStamp> {
+        self.client().get_expiry(&id)
+    }
+
+    pub fn is_expired(&self, id: BytesN<16>) -> bool {
+        self.client().is_expired(&id)
+    }
+
+    pub fn offices_of(&self, holder: Identifier) -> Vec<BytesN<16>> {
+        self.client().offices_of(&holder)
+    }
+
+    pub fn office_count(&self, holder: Identifier) -> u32 {
+        self.client().office_count(&holder)
+    }
+
+    pub fn offices(&self, start: u32, limit: u32) -> Vec<BytesN<16>> {
+        self.client().offices(&start, &limit)
+    }
+
+    pub fn offices_for_sale(&self, start: u32, limit: u32) -> Vec<BytesN<16>> {
+        self.client().offices_for_sale(&start, &limit)
+    }
+
+    pub fn offices_held(&self, start: u32, limit: u32) -> Vec<BytesN<16>> {
+        self.client().offices_held(&start, &limit)
+    }
+
+    pub fn get_tax(&self, id: BytesN<16>) -> BigInt {
+        self.client().get_tax(&id)
+    }
+
+    pub fn set_valuation(&self, id: BytesN<16>, holder: AccountId, valuation: BigInt) {
+        self.env.set_source_account(&holder);
+        self.client().set_valuation(
+            &id,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &valuation,
+        )
+    }
+
+    pub fn force_buy(
         &self,
-        admin: AccountId,
         id: BytesN<16>,
-        auction: BytesN<32>,
-        price: BigInt,
-        min_price: BigInt,
-        slope: BigInt,
+        buyer: AccountId,
+        valuation: BigInt,
+        max_price: BigInt,
     ) {
+        self.env.set_source_account(&buyer);
+        self.client().force_buy(
+            &id,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &valuation,
+            &max_price,
+        )
+    }
+
+    pub fn place_bid(&self, id: BytesN<16>, bidder: AccountId, amount: BigInt) {
+        self.env.set_source_account(&bidder);
+        self.client().place_bid(
+            &id,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &amount,
+        )
+    }
+
+    pub fn commit_bid(&self, id: BytesN<16>, bidder: AccountId, hash: BytesN<32>, deposit: BigInt) {
+        self.env.set_source_account(&bidder);
+        self.client().commit_bid(
+            &id,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &hash,
+            &deposit,
+        )
+    }
+
+    pub fn reveal_bid(&self, id: BytesN<16>, bidder: Identifier, amount: BigInt, salt: BytesN<32>) {
+        self.client().reveal_bid(&id, &bidder, &amount, &salt)
+    }
+
+    pub fn settle(&self, id: BytesN<16>) {
+        self.client().settle(&id)
+    }
+
+    pub fn pay_tax(&self, id: BytesN<16>, payer: AccountId) {
+        self.env.set_source_account(&payer);
+        self.client().pay_tax(
+            &id,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+        )
+    }
+
+    pub fn pay_tax_periods(&self, id: BytesN<16>, payer: AccountId, periods: u32) {
+        self.env.set_source_account(&payer);
+        self.client().pay_tax_periods(
+            &id,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &periods,
+        )
+    }
+
+    pub fn set_max_prepay(&self, admin: AccountId, horizon: TimeStamp) {
         self.env.set_source_account(&admin);
-        self.client().revoke(
+        self.client()
+            .set_max_prepay(&self.invoker_auths(), &horizon)
+    }
+
+    pub fn transfer(&self, id: BytesN<16>, from: AccountId, to: Identifier) {
+        self.env.set_source_account(&from);
+        self.client().transfer(
+            &id,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
+            &to,
+        )
+    }
+
+    pub fn set_heir(&self, id: BytesN<16>, holder: AccountId, heir: Identifier) {
+        self.env.set_source_account(&holder);
+        self.client().set_heir(
+            &id,
             &Auth {
                 sig: soroban_auth::Signature::Invoker,
                 nonce: BigInt::zero(&self.env),
             },
+            &heir,
+        )
+    }
+
+    pub fn claim_inheritance(&self, id: BytesN<16>, heir: AccountId) {
+        self.env.set_source_account(&heir);
+        self.client().claim_inheritance(
             &id,
-            &auction,
-            &price,
-            &min_price,
-            &slope,
+            &Auth {
+                sig: soroban_auth::Signature::Invoker,
+                nonce: BigInt::zero(&self.env),
+            },
         )
     }
+
+    pub fn set_claim_window(&self, admin: AccountId, window: TimeStamp) {
+        self.env.set_source_account(&admin);
+        self.client()
+            .set_claim_window(&self.invoker_auths(), &window)
+    }
+
+    pub fn set_tax(&self, admin: AccountId, amount: BigInt) {
+        self.env.set_source_account(&admin);
+        self.client().set_tax(&self.invoker_auths(), &amount)
+    }
+
+    pub fn set_period(&self, admin: AccountId, period: TimeStamp) {
+        self.env.set_source_account(&admin);
+        self.client().set_period(&self.invoker_auths(), &period)
+    }
+
+    pub fn set_grace(&self, admin: AccountId, window: TimeStamp, penalty: u32) {
+        self.env.set_source_account(&admin);
+        self.client()
+            .set_grace(&self.invoker_auths(), &window, &penalty)
+    }
+
+    pub fn revoke(
+        &self,
+        admin: AccountId,
+        id: BytesN<16>,
+        params: SaleParams,
+        tax: Option<Tax>,
+        period: Option<TimeStamp>,
+    ) {
+        self.env.set_source_account(&admin);
+        self.client()
+            .revoke(&self.invoker_auths(), &id, &params, &tax, &period)
+    }
 }